@@ -0,0 +1,73 @@
+//! Compares static data tables with values reported by the game and prints differences.
+//! Should be run after balance patches, e.g. `cargo run --example static-data -- local -m <map>`.
+
+use rust_sc2::{
+	prelude::*,
+	static_data::{UNIT_INFO, UPGRADE_INFO},
+};
+
+mod ex_main;
+
+#[bot]
+#[derive(Default)]
+struct StaticDataCheck;
+
+impl Player for StaticDataCheck {
+	fn on_start(&mut self) -> SC2Result<()> {
+		let mut mismatches = 0;
+		for (unit, info) in UNIT_INFO.iter() {
+			let data = match self.game_data.units.get(unit) {
+				Some(data) => data,
+				None => {
+					println!("{:?} is missing in game data", unit);
+					continue;
+				}
+			};
+			let expected = (
+				info.mineral_cost,
+				info.vespene_cost,
+				info.food_required,
+				info.build_time,
+				info.armor,
+				info.sight_range,
+				info.cargo_size,
+			);
+			let actual = (
+				data.mineral_cost,
+				data.vespene_cost,
+				data.food_required,
+				data.build_time,
+				data.armor,
+				data.sight_range,
+				data.cargo_size,
+			);
+			if expected != actual {
+				println!("{:?}: static {:?}, game {:?}", unit, expected, actual);
+				mismatches += 1;
+			}
+		}
+		for (upgrade, info) in UPGRADE_INFO.iter() {
+			let expected = (info.mineral_cost, info.vespene_cost, info.research_time);
+			match self.game_data.upgrades.get(upgrade) {
+				Some(data) => {
+					let actual = (data.mineral_cost, data.vespene_cost, data.research_time);
+					if expected != actual {
+						println!("{:?}: static {:?}, game {:?}", upgrade, expected, actual);
+						mismatches += 1;
+					}
+				}
+				None => println!("{:?} is missing in game data", upgrade),
+			}
+		}
+		println!("Found {} mismatches", mismatches);
+		self.leave()
+	}
+
+	fn get_player_settings(&self) -> PlayerSettings {
+		PlayerSettings::new(Race::Random).with_name("StaticDataCheck")
+	}
+}
+
+fn main() -> SC2Result<()> {
+	ex_main::main(StaticDataCheck::default())
+}
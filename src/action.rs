@@ -2,16 +2,18 @@
 
 use crate::{
 	geometry::{Point2, Point3},
-	ids::AbilityId,
+	ids::{AbilityId, ParseId},
 	FromProto, IntoProto,
 };
-use num_traits::{FromPrimitive, ToPrimitive};
+use num_traits::ToPrimitive;
 use rustc_hash::FxHashMap;
 use sc2_proto::{
 	error::ActionResult as ProtoActionResult,
 	raw::{ActionRawUnitCommand_oneof_target as ProtoTarget, ActionRaw_oneof_action as ProtoRawAction},
 	sc2api::{Action as ProtoAction, ActionChat_Channel, ActionError as ProtoActionError},
 };
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 // pub(crate) type Command = (u64, (AbilityId, Target, bool));
 
@@ -23,6 +25,7 @@ pub(crate) struct Commander {
 
 /// Target of ability used by unit.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Target {
 	/// Ability target is position (move, build, ...).
 	Pos(Point2),
@@ -34,6 +37,7 @@ pub enum Target {
 
 #[doc(hidden)]
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Action {
 	UnitCommand(AbilityId, Target, Vec<u64>, bool),
 	CameraMove(Point3),
@@ -85,11 +89,7 @@ impl FromProto<&ProtoAction> for Option<Action> {
 		if action.has_action_raw() {
 			match &action.get_action_raw().action {
 				Some(ProtoRawAction::unit_command(unit_command)) => Some(Action::UnitCommand(
-					{
-						let id = unit_command.get_ability_id();
-						AbilityId::from_i32(id)
-							.unwrap_or_else(|| panic!("There's no `AbilityId` with value {}", id))
-					},
+					AbilityId::parse_id(unit_command.get_ability_id())?,
 					match &unit_command.target {
 						Some(ProtoTarget::target_world_space_pos(pos)) => {
 							Target::Pos(Point2::from_proto(pos))
@@ -104,14 +104,10 @@ impl FromProto<&ProtoAction> for Option<Action> {
 					Point3::from_proto(camera_move.get_center_world_space()),
 				)),
 				Some(ProtoRawAction::toggle_autocast(toggle_autocast)) => Some(Action::ToggleAutocast(
-					{
-						let id = toggle_autocast.get_ability_id();
-						AbilityId::from_i32(id)
-							.unwrap_or_else(|| panic!("There's no `AbilityId` with value {}", id))
-					},
+					AbilityId::parse_id(toggle_autocast.get_ability_id())?,
 					toggle_autocast.get_unit_tags().to_vec(),
 				)),
-				None => unreachable!(),
+				None => None,
 			}
 		} else if action.has_action_chat() {
 			let chat = action.get_action_chat();
@@ -130,22 +126,25 @@ impl FromProto<&ProtoAction> for Option<Action> {
 /// Structure used to analyze actions failed on previous game step.
 /// Stored in [`state.action_errors`](crate::game_state::GameState::action_errors).
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ActionError {
 	/// Tag of unit that was executing action.
 	pub unit: u64,
 	/// Ability that was used by this unit.
+	/// `NullNull` if ability is unknown, its id is kept in [`raw_ability`](Self::raw_ability).
 	pub ability: AbilityId,
+	/// Raw id of ability that was used by this unit.
+	pub raw_ability: u64,
 	/// Result of executed action.
 	pub result: ActionResult,
 }
 impl FromProto<&ProtoActionError> for ActionError {
 	fn from_proto(e: &ProtoActionError) -> Self {
+		let raw_ability = e.get_ability_id();
 		Self {
 			unit: e.get_unit_tag(),
-			ability: {
-				let id = e.get_ability_id();
-				AbilityId::from_u64(id).unwrap_or_else(|| panic!("There's no `AbilityId` with value {}", id))
-			},
+			ability: AbilityId::parse_id(raw_ability).unwrap_or(AbilityId::NullNull),
+			raw_ability,
 			result: ActionResult::from_proto(e.get_result()),
 		}
 	}
@@ -154,6 +153,7 @@ impl FromProto<&ProtoActionError> for ActionError {
 /// Result of executed action.
 #[allow(missing_docs)]
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ActionResult {
 	Success,
 	NotSupported,
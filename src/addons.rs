@@ -0,0 +1,235 @@
+//! Helpers for terran addons: reserving addon space, landing and addon swaps.
+//!
+//! Swaps are planned with [`Bot::swap_addon`], stored in [`addon_swaps`](crate::bot::Bot::addon_swaps)
+//! field and executed step by step with [`Bot::execute_addon_swaps`]:
+//! ```
+//! use rust_sc2::prelude::*;
+//!
+//! #[bot]
+//! #[derive(Default)]
+//! struct MyBot;
+//! impl MyBot {
+//!     fn on_step(&mut self) {
+//!         let structures = &self.units.my.structures;
+//!         let barracks = structures.of_type(UnitTypeId::Barracks).filter(|b| b.has_techlab());
+//!         let factory = structures.of_type(UnitTypeId::Factory).filter(|f| !f.has_addon());
+//!         if let (Some(barracks), Some(factory)) = (barracks.first(), factory.first()) {
+//!             let (barracks, factory) = (barracks.tag(), factory.tag());
+//!             if !self.addon_swaps.is_swapping(barracks) && !self.addon_swaps.is_swapping(factory) {
+//!                 self.swap_addon(barracks, factory);
+//!             }
+//!         }
+//!         self.execute_addon_swaps();
+//!     }
+//! }
+//! ```
+
+use crate::{
+	bot::{Bot, PlacementOptions},
+	consts::UNIT_ALIAS,
+	geometry::Point2,
+	ids::UnitTypeId,
+	unit::Unit,
+};
+use std::mem;
+
+/// Structures which can have addons.
+const ADDON_PRODUCERS: [UnitTypeId; 6] = [
+	UnitTypeId::Barracks,
+	UnitTypeId::Factory,
+	UnitTypeId::Starport,
+	UnitTypeId::BarracksFlying,
+	UnitTypeId::FactoryFlying,
+	UnitTypeId::StarportFlying,
+];
+
+/// Returns center of addon of structure placed on given position.
+pub fn addon_position(pos: Point2) -> Point2 {
+	pos.offset(2.5, -0.5)
+}
+
+/// Stage of addon swap.
+#[variant_checkers]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum SwapState {
+	/// Structures are lifting off.
+	Lifting,
+	/// Structures are landing on each other's places.
+	Landing,
+	/// Swap completed, structure which had no addon now has it.
+	Done,
+	/// One of structures died or can't take part in swap.
+	Failed,
+}
+
+/// Swap of addon between two structures.
+#[derive(Debug, Copy, Clone)]
+pub struct AddonSwap {
+	/// Tag of structure which has addon.
+	pub from: u64,
+	/// Tag of structure which will take addon.
+	pub to: u64,
+	/// Position of structure with addon (where addon is).
+	pub from_pos: Point2,
+	/// Position of structure which will take addon.
+	pub to_pos: Point2,
+	/// Current stage of the swap.
+	pub state: SwapState,
+	/// Game loop when swap was started.
+	pub started: u32,
+}
+
+/// All planned and ongoing addon swaps.
+#[derive(Debug, Default, Clone)]
+pub struct AddonSwaps {
+	swaps: Vec<AddonSwap>,
+}
+impl AddonSwaps {
+	/// All swaps, including completed and failed on last execution.
+	pub fn swaps(&self) -> &[AddonSwap] {
+		&self.swaps
+	}
+	/// Checks if structure with given tag takes part in unfinished swap.
+	pub fn is_swapping(&self, tag: u64) -> bool {
+		self.swaps.iter().any(|s| {
+			(s.from == tag || s.to == tag) && matches!(s.state, SwapState::Lifting | SwapState::Landing)
+		})
+	}
+	/// Cancels swap of structure with given tag. Structures are left where they are.
+	pub fn cancel(&mut self, tag: u64) {
+		self.swaps.retain(|s| s.from != tag && s.to != tag);
+	}
+}
+
+impl Bot {
+	/// Checks if building of given type placed on given position would block
+	/// addon space of own barracks, factories and starports without addons.
+	pub fn blocks_addon(&self, building: UnitTypeId, pos: Point2) -> bool {
+		let half = self
+			.game_data
+			.units
+			.get(&building)
+			.and_then(|data| data.ability)
+			.and_then(|ability| self.game_data.abilities.get(&ability))
+			.and_then(|data| data.footprint_radius)
+			.unwrap_or(0.0);
+		let swap_spots = self
+			.addon_swaps
+			.swaps
+			.iter()
+			.filter(|s| s.state.is_lifting() || s.state.is_landing())
+			.flat_map(|s| [s.from_pos, s.to_pos]);
+
+		self.units
+			.my
+			.structures
+			.of_types(&&ADDON_PRODUCERS[..3])
+			.iter()
+			.filter(|u| !u.has_addon())
+			.map(|u| u.position())
+			.chain(swap_spots)
+			.map(addon_position)
+			.any(|addon| (addon.x - pos.x).abs() < half + 1.0 && (addon.y - pos.y).abs() < half + 1.0)
+	}
+
+	/// Finds position near given one, where flying structure can land with room for addon.
+	pub fn find_landing_spot(&self, building: &Unit, near: Point2) -> Option<Point2> {
+		let landed = UNIT_ALIAS
+			.get(&building.type_id())
+			.copied()
+			.unwrap_or_else(|| building.type_id());
+		self.find_placement(
+			landed,
+			near,
+			PlacementOptions {
+				addon: true,
+				reserve_addons: true,
+				..Default::default()
+			},
+		)
+	}
+
+	/// Plans swap of addon from structure `from` to structure `to`:
+	/// both lift off, then `to` lands on the place of `from` and `from` lands on the place of `to`.
+	///
+	/// Returns `false` if swap can't be started:
+	/// structures aren't barracks, factories or starports, `from` has no addon,
+	/// `to` already has one, or one of them is already swapping.
+	pub fn swap_addon(&mut self, from: u64, to: u64) -> bool {
+		let structures = &self.units.my.structures;
+		let (a, b) = match (structures.get(from), structures.get(to)) {
+			(Some(a), Some(b)) => (a, b),
+			_ => return false,
+		};
+		let can_swap = ADDON_PRODUCERS.contains(&a.type_id())
+			&& ADDON_PRODUCERS.contains(&b.type_id())
+			&& a.has_addon()
+			&& !b.has_addon();
+		if !can_swap || self.addon_swaps.is_swapping(from) || self.addon_swaps.is_swapping(to) {
+			return false;
+		}
+
+		let swap = AddonSwap {
+			from,
+			to,
+			from_pos: a.position(),
+			to_pos: b.position(),
+			state: SwapState::Lifting,
+			started: self.state.observation.game_loop(),
+		};
+		self.addon_swaps.swaps.push(swap);
+		true
+	}
+
+	/// Gives orders to structures taking part in addon swaps and updates states of swaps.
+	/// Completed and failed swaps are kept until next execution, so their results can be checked.
+	pub fn execute_addon_swaps(&mut self) {
+		let mut swaps = mem::take(&mut self.addon_swaps.swaps);
+		swaps.retain(|s| s.state.is_lifting() || s.state.is_landing());
+
+		for swap in &mut swaps {
+			let structures = &self.units.my.structures;
+			let (a, b) = match (structures.get(swap.from), structures.get(swap.to)) {
+				(Some(a), Some(b)) => (a, b),
+				_ => {
+					swap.state = SwapState::Failed;
+					continue;
+				}
+			};
+
+			match swap.state {
+				SwapState::Lifting => {
+					if a.is_flying() && b.is_flying() {
+						a.land(swap.to_pos, false);
+						b.land(swap.from_pos, false);
+						swap.state = SwapState::Landing;
+					} else {
+						for u in [a, b] {
+							if !u.is_flying() && u.is_idle() {
+								u.lift(false);
+							}
+						}
+					}
+				}
+				SwapState::Landing => {
+					if !a.is_flying() && !b.is_flying() {
+						swap.state = if b.has_addon() {
+							SwapState::Done
+						} else {
+							SwapState::Failed
+						};
+					} else {
+						for (u, pos) in [(a, swap.to_pos), (b, swap.from_pos)] {
+							if u.is_flying() && u.is_idle() {
+								u.land(pos, false);
+							}
+						}
+					}
+				}
+				_ => {}
+			}
+		}
+
+		self.addon_swaps.swaps = swaps;
+	}
+}
@@ -0,0 +1,255 @@
+//! Target optimizer for area of effect spells and splash attacks.
+//!
+//! Generic optimizers are [`Units::best_aoe_point`] and [`Units::best_aoe_unit`],
+//! while [`AoeSpell`] presets are used by [`Bot::best_spell_target`]:
+//! ```
+//! use rust_sc2::{aoe::AoeSpell, prelude::*};
+//!
+//! #[bot]
+//! #[derive(Default)]
+//! struct MyBot;
+//! impl MyBot {
+//!     fn storm(&mut self) {
+//!         for templar in &self.units.my.units.of_type(UnitTypeId::HighTemplar) {
+//!             if let Some((target, value)) = self.best_spell_target(templar, AoeSpell::PsiStorm) {
+//!                 if value >= 300.0 {
+//!                     templar.command(AoeSpell::PsiStorm.ability(), target, false);
+//!                 }
+//!             }
+//!         }
+//!     }
+//! }
+//! ```
+
+use crate::{
+	action::Target,
+	bot::Bot,
+	distance::*,
+	game_data::{GameData, TargetType},
+	geometry::Point2,
+	ids::{AbilityId, EffectId},
+	unit::Unit,
+	units::Units,
+};
+
+impl Units {
+	/// Finds position where circle of given radius covers units with the highest total value.
+	///
+	/// `value` is called for every unit in collection, so negative values can be used
+	/// to avoid friendly fire. Only positions satisfying `in_range` are considered
+	/// (e.g. checked with [`in_ability_cast_range`](Unit::in_ability_cast_range)).
+	///
+	/// Returns best position and covered value or `None` if no position has positive value.
+	pub fn best_aoe_point<V, R>(&self, radius: f32, value: V, in_range: R) -> Option<(Point2, f32)>
+	where
+		V: Fn(&Unit) -> f32,
+		R: Fn(Point2) -> bool,
+	{
+		let values = self.aoe_values(value);
+
+		// Candidates are positions of units with positive value and midpoints between them
+		let positive = values
+			.iter()
+			.filter(|(_, _, v)| *v > 0.0)
+			.map(|(p, _, _)| *p)
+			.collect::<Vec<_>>();
+		let mut candidates = positive.clone();
+		for (i, a) in positive.iter().enumerate() {
+			for b in &positive[i + 1..] {
+				if a.is_closer(radius * 2.0, *b) {
+					candidates.push((*a + *b) / 2.0);
+				}
+			}
+		}
+
+		candidates
+			.into_iter()
+			.filter(|p| in_range(*p))
+			.map(|p| (p, covered_value(&values, radius, p)))
+			.filter(|(_, total)| *total > 0.0)
+			.max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())
+	}
+	/// Finds unit to shoot, so splash of given radius around it covers units with the highest total value.
+	///
+	/// Same as [`best_aoe_point`](Self::best_aoe_point), but for attacks targeting units
+	/// (e.g. Siege Tank or Widow Mine), so only positions of units with positive value
+	/// satisfying `in_range` are considered.
+	///
+	/// Returns tag of the best primary target and covered value or `None` if no target has positive value.
+	pub fn best_aoe_unit<V, R>(&self, radius: f32, value: V, in_range: R) -> Option<(u64, f32)>
+	where
+		V: Fn(&Unit) -> f32,
+		R: Fn(&Unit) -> bool,
+	{
+		let values = self.aoe_values(value);
+
+		self.iter()
+			.zip(&values)
+			.filter(|(u, (_, _, v))| *v > 0.0 && in_range(u))
+			.map(|(u, _)| (u.tag(), covered_value(&values, radius, u.position())))
+			.max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())
+	}
+
+	fn aoe_values<V: Fn(&Unit) -> f32>(&self, value: V) -> Vec<(Point2, f32, f32)> {
+		self.iter()
+			.map(|u| (u.position(), u.radius(), value(u)))
+			.collect()
+	}
+}
+
+fn covered_value(values: &[(Point2, f32, f32)], radius: f32, center: Point2) -> f32 {
+	values
+		.iter()
+		.filter(|(pos, r, _)| pos.is_closer(radius + r, center))
+		.map(|(_, _, v)| v)
+		.sum()
+}
+
+/// Presets of area of effect spells and splash attacks.
+#[variant_checkers]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum AoeSpell {
+	/// High Templar's Psionic Storm.
+	PsiStorm,
+	/// Infestor's Fungal Growth.
+	FungalGrowth,
+	/// Ghost's EMP Round.
+	EMP,
+	/// Ravager's Corrosive Bile.
+	CorrosiveBile,
+	/// Widow Mine's Sentinel Missiles.
+	WidowMine,
+	/// Sieged Tank's Crushing Grip (splash of its attack).
+	SiegeTank,
+}
+impl AoeSpell {
+	/// Ability used to cast the spell.
+	pub fn ability(self) -> AbilityId {
+		match self {
+			AoeSpell::PsiStorm => AbilityId::PsiStormPsiStorm,
+			AoeSpell::FungalGrowth => AbilityId::FungalGrowthFungalGrowth,
+			AoeSpell::EMP => AbilityId::EMPEMP,
+			AoeSpell::CorrosiveBile => AbilityId::EffectCorrosiveBile,
+			AoeSpell::WidowMine => AbilityId::WidowMineAttackWidowMineAttack,
+			AoeSpell::SiegeTank => AbilityId::Attack,
+		}
+	}
+	/// Effect created by the spell, if any.
+	pub fn effect(self) -> Option<EffectId> {
+		match self {
+			AoeSpell::PsiStorm => Some(EffectId::PsiStormPersistent),
+			AoeSpell::CorrosiveBile => Some(EffectId::RavagerCorrosiveBileCP),
+			_ => None,
+		}
+	}
+	/// `true` if spell is cast on unit, so [`Target::Tag`] is used instead of position.
+	pub fn targets_unit(self) -> bool {
+		matches!(self, AoeSpell::WidowMine | AoeSpell::SiegeTank)
+	}
+	/// Radius of the spell.
+	///
+	/// Taken from effect's data or from ability's footprint when game data has them.
+	/// Otherwise falls back to the known in-game values, since the API doesn't
+	/// expose splash radius for most spells and weapons.
+	pub fn radius(self, game_data: &GameData) -> f32 {
+		self.effect()
+			.and_then(|e| game_data.effects.get(&e))
+			.map(|data| data.radius)
+			.or_else(|| {
+				game_data
+					.abilities
+					.get(&self.ability())
+					.and_then(|data| data.footprint_radius)
+					.filter(|radius| *radius > 0.0)
+			})
+			.unwrap_or(match self {
+				AoeSpell::PsiStorm => 1.5,
+				AoeSpell::FungalGrowth => 2.25,
+				AoeSpell::EMP => 1.5,
+				AoeSpell::CorrosiveBile => 0.5,
+				AoeSpell::WidowMine => 1.75,
+				AoeSpell::SiegeTank => 1.25,
+			})
+	}
+	/// Units affected by the spell.
+	pub fn target(self) -> TargetType {
+		match self {
+			AoeSpell::SiegeTank => TargetType::Ground,
+			_ => TargetType::Any,
+		}
+	}
+	/// `true` if spell damages caster's units too.
+	pub fn friendly_fire(self) -> bool {
+		!matches!(self, AoeSpell::FungalGrowth)
+	}
+}
+
+impl Bot {
+	/// Finds best target of given spell for given caster.
+	///
+	/// Value of unit is its cost (minerals + vespene): enemies count positively,
+	/// and your or allied units count negatively if spell has friendly fire.
+	/// Snapshots and units not affected by the spell are ignored.
+	///
+	/// Returns best target and expected value or `None` if there's nothing worth casting at.
+	/// Target is [`Target::Tag`] of primary target for spells [targeting units](AoeSpell::targets_unit)
+	/// and [`Target::Pos`] for others, so it can be passed to [`command`](Unit::command) directly.
+	pub fn best_spell_target(&self, caster: &Unit, spell: AoeSpell) -> Option<(Target, f32)> {
+		let radius = spell.radius(&self.game_data);
+		let target = spell.target();
+		let friendly_fire = spell.friendly_fire();
+		let search_range = match spell {
+			AoeSpell::SiegeTank => caster.ground_range(),
+			_ => self
+				.game_data
+				.abilities
+				.get(&spell.ability())
+				.and_then(|data| data.cast_range)
+				.unwrap_or(0.0),
+		} + caster.radius()
+			+ radius * 2.0;
+
+		let units = self.units.all.filter(|u| {
+			!(u.is_snapshot() || u.is_neutral()) && u.is_closer(search_range + u.radius(), caster)
+		});
+
+		let value = |u: &Unit| {
+			let affected = match target {
+				TargetType::Any => true,
+				TargetType::Air => u.is_flying(),
+				TargetType::Ground => !u.is_flying(),
+			};
+			if !affected {
+				return 0.0;
+			}
+
+			let cost = u.cost();
+			let cost = (cost.minerals + cost.vespene) as f32;
+			if u.is_enemy() {
+				cost
+			} else if friendly_fire {
+				-cost
+			} else {
+				0.0
+			}
+		};
+
+		match spell {
+			AoeSpell::SiegeTank => units
+				.best_aoe_unit(radius, value, |u| {
+					caster.in_range(u, 0.0) && caster.is_further(2.0 + caster.radius() + u.radius(), u)
+				})
+				.map(|(tag, value)| (Target::Tag(tag), value)),
+			AoeSpell::WidowMine => units
+				.best_aoe_unit(radius, value, |u| {
+					caster.in_ability_cast_range(spell.ability(), u, 0.0)
+				})
+				.map(|(tag, value)| (Target::Tag(tag), value)),
+			_ => units
+				.best_aoe_point(radius, value, |p| {
+					caster.in_ability_cast_range(spell.ability(), p, 0.0)
+				})
+				.map(|(pos, value)| (Target::Pos(pos), value)),
+		}
+	}
+}
@@ -2,9 +2,16 @@
 
 use crate::{
 	action::{Action, ActionResult, Commander, Target},
+	addons::{addon_position, AddonSwaps},
 	api::API,
+	chrono::ChronoScheduler,
 	client::SC2Result,
-	consts::{RaceValues, FRAMES_PER_SECOND, INHIBITOR_IDS, RACE_VALUES, TECH_ALIAS, UNIT_ALIAS},
+	console::Console,
+	consts::{
+		RaceValues, FRAMES_PER_SECOND, INHIBITOR_IDS, LARVA_UNITS, RACE_VALUES, TECH_ALIAS, UNIT_ALIAS,
+	},
+	creep::CreepPlanner,
+	danger::DangerZones,
 	debug::{DebugCommand, Debugger},
 	distance::*,
 	game_data::{Cost, GameData},
@@ -13,14 +20,24 @@ use crate::{
 	game_state::{Alliance, GameState},
 	geometry::Point2,
 	ids::{AbilityId, EffectId, UnitTypeId, UpgradeId},
+	orbital::OrbitalManager,
+	pixel_map::LastSeenMap,
 	player::Race,
 	ramp::{Ramp, Ramps},
-	unit::{DataForUnit, SharedUnitData, Unit},
+	roles::Roles,
+	scenario::Scenario,
+	squads::Squads,
+	static_data::{unit_info, upgrade_info},
+	strategy::StrategyInference,
+	transport::Transports,
+	unit::{AbilityCooldown, DataForUnit, SharedUnitData, Unit},
 	units::{AllUnits, Units},
 	utils::{dbscan, range_query},
+	zerg::ZergManager,
 	FromProto, IntoProto,
 };
 use indexmap::IndexSet;
+use ndarray::Zip;
 use num_traits::ToPrimitive;
 use rand::prelude::*;
 use rustc_hash::{FxHashMap, FxHashSet, FxHasher};
@@ -28,7 +45,9 @@ use sc2_proto::{
 	query::{RequestQueryBuildingPlacement, RequestQueryPathing},
 	sc2api::Request,
 };
-use std::{fmt, hash::BuildHasherDefault, process::Child};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::{fmt, hash::BuildHasherDefault, mem, process::Child};
 
 type FxIndexSet<T> = IndexSet<T, BuildHasherDefault<FxHasher>>;
 
@@ -173,6 +192,7 @@ impl LockOwned<u32> for LockU32 {
 
 /// Information about an expansion location.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Expansion {
 	/// Placement position for townhall.
 	pub loc: Point2,
@@ -201,6 +221,8 @@ pub struct PlacementOptions {
 	pub random: bool,
 	/// Filter positions where addon can fit. [Default: `false`]
 	pub addon: bool,
+	/// Filter positions which block addon space of own structures. [Default: `false`]
+	pub reserve_addons: bool,
 }
 impl Default for PlacementOptions {
 	fn default() -> Self {
@@ -209,6 +231,7 @@ impl Default for PlacementOptions {
 			step: 2,
 			random: false,
 			addon: false,
+			reserve_addons: false,
 		}
 	}
 }
@@ -426,6 +449,31 @@ pub struct Bot {
 	pub enemies_ordered: FxHashMap<UnitTypeId, usize>,
 	/// Ready enemy units counted by unit type.
 	pub enemies_current: FxHashMap<UnitTypeId, usize>,
+	/// Opponent's opening inferred from scouting information.
+	pub strategy: StrategyInference,
+	/// Named groups of owned units.
+	pub squads: Squads,
+	/// Roles claimed for owned units.
+	pub roles: Roles,
+	/// Danger zones of effects and explosive units.
+	pub danger_zones: DangerZones,
+	/// Tracker of own creep tumors and creep coverage.
+	pub creep_planner: CreepPlanner,
+	/// Manager of queens, injects and larva.
+	pub zerg: ZergManager,
+	/// Manager of orbital commands' energy and scan requests.
+	pub orbitals: OrbitalManager,
+	/// Planned and ongoing addon swaps.
+	pub addon_swaps: AddonSwaps,
+	/// Scheduler of chrono boost.
+	pub chrono: ChronoScheduler,
+	/// Planned and ongoing drops and passengers of own transports.
+	pub transports: Transports,
+	/// Console of chat commands for live debugging.
+	pub console: Console,
+	/// Micro scenario played with Debug API, see [`run_scenario`](Self::run_scenario).
+	pub scenario: Option<Scenario>,
+	camera_follow: Option<u64>,
 	pub(crate) saved_hallucinations: FxHashSet<u64>,
 	/// In-game time in seconds.
 	pub time: f32,
@@ -461,10 +509,13 @@ pub struct Bot {
 	pub vision_blockers: Vec<Point2>,
 	/// Ramps on map.
 	pub ramps: Ramps,
-	enemy_upgrades: Rw<FxHashSet<UpgradeId>>,
+	/// Game loops, when each position of the map was visible last time.
+	pub last_seen: LastSeenMap,
+	pub(crate) enemy_upgrades: Rw<FxHashSet<UpgradeId>>,
 	pub(crate) owned_tags: FxHashSet<u64>,
 	pub(crate) under_construction: FxHashSet<u64>,
 	pub(crate) available_frames: Rw<FxHashMap<u64, u32>>,
+	pub(crate) ability_cooldowns: Rw<FxHashMap<u64, FxHashMap<AbilityId, AbilityCooldown>>>,
 }
 
 impl Bot {
@@ -523,6 +574,7 @@ impl Bot {
 		CountOptions::new(self, true)
 	}
 	pub(crate) fn get_actions(&mut self) -> &[Action] {
+		self.follow_camera();
 		let actions = &mut self.actions;
 
 		let mut commander = self.commander.write_lock();
@@ -552,17 +604,20 @@ impl Bot {
 		self.actions.clear();
 	}
 	pub(crate) fn get_debug_commands(&mut self) -> &[DebugCommand] {
-		self.debug.get_commands()
+		self.draw_overlays();
+		self.debug.get_commands(self.state.observation.game_loop())
 	}
 	pub(crate) fn clear_debug_commands(&mut self) {
 		self.debug.clear_commands();
 	}
 	/// Returns full cost of building given unit type, without any corrections.
+	///
+	/// Falls back to [`static_data`](crate::static_data) when game data lacks the unit type.
 	pub fn get_unit_api_cost(&self, unit: UnitTypeId) -> Cost {
-		self.game_data
-			.units
-			.get(&unit)
-			.map_or_else(Cost::default, |data| data.cost())
+		self.game_data.units.get(&unit).map_or_else(
+			|| unit_info(unit).map_or_else(Cost::default, |info| info.cost()),
+			|data| data.cost(),
+		)
 	}
 	/// Returns correct cost of building given unit type.
 	pub fn get_unit_cost(&self, unit: UnitTypeId) -> Cost {
@@ -625,10 +680,10 @@ impl Bot {
 	}
 	/// Checks cost of making given upgrade.
 	pub fn get_upgrade_cost(&self, upgrade: UpgradeId) -> Cost {
-		self.game_data
-			.upgrades
-			.get(&upgrade)
-			.map_or_else(Default::default, |data| data.cost())
+		self.game_data.upgrades.get(&upgrade).map_or_else(
+			|| upgrade_info(upgrade).map_or_else(Default::default, |info| info.cost()),
+			|data| data.cost(),
+		)
 	}
 	/// Checks if bot has enough resources to make given upgrade.
 	pub fn can_afford_upgrade(&self, upgrade: UpgradeId) -> bool {
@@ -720,6 +775,38 @@ impl Bot {
 	pub fn chat_ally(&mut self, message: &str) {
 		self.actions.push(Action::Chat(message.to_string(), true));
 	}
+	/// Moves camera to given position.
+	pub fn move_camera(&mut self, pos: Point2) {
+		let z = self.get_z_height(pos);
+		self.actions.push(Action::CameraMove(pos.to3(z)));
+	}
+	/// Keeps camera on unit with given tag, until unit dies or
+	/// [`stop_following`](Self::stop_following) is called.
+	///
+	/// Useful when watching games or playing against bot with [`run_vs_human`](crate::client::run_vs_human).
+	pub fn follow_unit(&mut self, tag: u64) {
+		self.camera_follow = Some(tag);
+	}
+	/// Stops keeping camera on unit.
+	pub fn stop_following(&mut self) {
+		self.camera_follow = None;
+	}
+	/// Returns tag of unit camera follows.
+	pub fn followed_unit(&self) -> Option<u64> {
+		self.camera_follow
+	}
+	fn follow_camera(&mut self) {
+		if let Some(tag) = self.camera_follow {
+			match self.units.all.get(tag).map(|u| u.position()) {
+				Some(pos) => {
+					if self.state.observation.raw.camera.distance_squared(pos) > 1.0 {
+						self.move_camera(pos);
+					}
+				}
+				None => self.camera_follow = None,
+			}
+		}
+	}
 	/// Returns actual terrain height on given position in 3D space.
 	pub fn get_z_height<P: Into<(usize, usize)>>(&self, pos: P) -> f32 {
 		self.game_info
@@ -795,6 +882,17 @@ impl Bot {
 			.get(pos.into())
 			.map_or(false, |p| p.is_explored())
 	}
+	/// Returns game loop, when given position was visible last time.
+	/// `None` if position was never visible.
+	pub fn last_seen<P: Into<(usize, usize)>>(&self, pos: P) -> Option<u32> {
+		self.last_seen.get(pos.into()).copied().flatten()
+	}
+	/// Returns amount of game loops passed since given position was visible last time.
+	/// `None` if position was never visible.
+	pub fn staleness<P: Into<(usize, usize)>>(&self, pos: P) -> Option<u32> {
+		self.last_seen(pos)
+			.map(|frame| self.state.observation.game_loop().saturating_sub(frame))
+	}
 	/// Checks if given position has zerg's creep.
 	pub fn has_creep<P: Into<(usize, usize)>>(&self, pos: P) -> bool {
 		self.state
@@ -812,7 +910,13 @@ impl Bot {
 			self.enemy_race = self.game_info.players[&enemy_player_id].race_requested;
 			self.enemy_player_id = enemy_player_id;
 		}
-		self.race_values = Rs::new(RACE_VALUES[&self.race].clone());
+		self.link_data_for_unit();
+	}
+	/// Shares bot's data with units, must be called after race detected.
+	pub(crate) fn link_data_for_unit(&mut self) {
+		if let Some(race_values) = RACE_VALUES.get(&self.race) {
+			self.race_values = Rs::new(race_values.clone());
+		}
 
 		self.data_for_unit = Rs::new(DataForUnit {
 			commander: Rs::clone(&self.commander),
@@ -829,6 +933,7 @@ impl Bot {
 			game_step: Rs::clone(&self.game_step),
 			game_loop: Rs::clone(&self.state.observation.game_loop),
 			available_frames: Rs::clone(&self.available_frames),
+			ability_cooldowns: Rs::clone(&self.ability_cooldowns),
 		});
 	}
 	pub(crate) fn prepare_start(&mut self) {
@@ -1070,6 +1175,20 @@ impl Bot {
 		self.supply_used = common.food_used;
 		self.supply_left = self.supply_cap.saturating_sub(self.supply_used);
 
+		// Updating visibility history
+		let game_loop = observation.game_loop();
+		let visibility = &observation.raw.visibility;
+		if self.last_seen.dim() != visibility.dim() {
+			self.last_seen = LastSeenMap::default(visibility.dim());
+		}
+		Zip::from(&mut self.last_seen)
+			.and(visibility)
+			.for_each(|last, v| {
+				if v.is_visible() {
+					*last = Some(game_loop);
+				}
+			});
+
 		// Counting units and orders
 		let mut current_units = FxHashMap::default();
 		let mut orders = FxHashMap::default();
@@ -1087,7 +1206,12 @@ impl Bot {
 							constructed.insert((pos, ability), false);
 						};
 					}
-					*orders.entry(ability).or_default() += 1;
+					// Zerglings are hatched in pairs
+					let amount = match LARVA_UNITS.get(&ability) {
+						Some((_, amount)) if u.type_id() == UnitTypeId::Egg => *amount,
+						_ => 1,
+					};
+					*orders.entry(ability).or_default() += amount;
 				}
 
 				if u.is_ready() {
@@ -1105,6 +1229,28 @@ impl Bot {
 		}
 		self.current_units = current_units;
 		self.orders = orders;
+		self.squads.update(&self.units.my.all);
+		self.transports.update(&self.units.my.all);
+		self.roles.update(&self.units.my.all, game_loop);
+		self.danger_zones
+			.update(&self.state.observation.raw.effects, &self.units.all, game_loop);
+
+		let mut strategy = mem::take(&mut self.strategy);
+		strategy.update(self);
+		self.strategy = strategy;
+
+		let mut creep_planner = mem::take(&mut self.creep_planner);
+		creep_planner.update(self);
+		self.creep_planner = creep_planner;
+
+		let mut zerg = mem::take(&mut self.zerg);
+		zerg.update(self);
+		self.zerg = zerg;
+		self.orbitals.update(game_loop);
+
+		let mut chrono = mem::take(&mut self.chrono);
+		chrono.update(self);
+		self.chrono = chrono;
 	}
 	pub(crate) fn update_units(&mut self, all_units: Units) {
 		*self.last_units_health.write_lock() = self
@@ -1540,7 +1686,7 @@ impl Bot {
 						if addon {
 							vec![
 								(ability, near, None),
-								(AbilityId::TerranBuildSupplyDepot, near.offset(2.5, -0.5), None),
+								(AbilityId::TerranBuildSupplyDepot, addon_position(near), None),
 							]
 						} else {
 							vec![(ability, near, None)]
@@ -1550,6 +1696,7 @@ impl Bot {
 					.unwrap()
 					.iter()
 					.all(|r| matches!(r, ActionResult::Success))
+					&& !(options.reserve_addons && self.blocks_addon(building, near))
 				{
 					return Some(near);
 				}
@@ -1589,7 +1736,7 @@ impl Bot {
 								valid_positions
 									.iter()
 									.map(|pos| {
-										(AbilityId::TerranBuildSupplyDepot, pos.offset(2.5, -0.5), None)
+										(AbilityId::TerranBuildSupplyDepot, addon_position(*pos), None)
 									})
 									.collect(),
 								false,
@@ -1603,6 +1750,10 @@ impl Bot {
 							.collect::<Vec<Point2>>();
 					}
 
+					if options.reserve_addons {
+						valid_positions.retain(|pos| !self.blocks_addon(building, *pos));
+					}
+
 					if !valid_positions.is_empty() {
 						return if options.random {
 							valid_positions.choose(&mut thread_rng()).copied()
@@ -1818,13 +1969,28 @@ impl Default for Bot {
 			last_units_health: Default::default(),
 			vision_blockers: Default::default(),
 			ramps: Default::default(),
+			last_seen: Default::default(),
 			enemy_upgrades: Default::default(),
 			owned_tags: Default::default(),
 			under_construction: Default::default(),
 			enemies_ordered: Default::default(),
 			enemies_current: Default::default(),
+			strategy: StrategyInference::new(),
+			squads: Default::default(),
+			roles: Default::default(),
+			danger_zones: Default::default(),
+			creep_planner: Default::default(),
+			zerg: Default::default(),
+			orbitals: Default::default(),
+			addon_swaps: Default::default(),
+			chrono: Default::default(),
+			transports: Default::default(),
+			console: Default::default(),
+			scenario: None,
+			camera_follow: None,
 			saved_hallucinations: Default::default(),
 			available_frames: Default::default(),
+			ability_cooldowns: Default::default(),
 		}
 	}
 }
@@ -0,0 +1,239 @@
+//! Scheduler of protoss chrono boost.
+//!
+//! Bot keeps [`ChronoScheduler`] in [`chrono`](crate::bot::Bot::chrono) field.
+//! [`Bot::chrono_boost`] spends energy of nexuses on structures chosen by
+//! [`priority`](ChronoScheduler::priority), and [`Bot::production_eta`]
+//! estimates when production will finish, taking chrono boosts into account:
+//! ```
+//! use rust_sc2::{chrono::ChronoTarget, prelude::*};
+//!
+//! #[bot]
+//! #[derive(Default)]
+//! struct MyBot;
+//! impl MyBot {
+//!     fn on_start(&mut self) {
+//!         self.chrono.priority = vec![
+//!             ChronoTarget::WarpGate,
+//!             ChronoTarget::Workers,
+//!             ChronoTarget::Structure(UnitTypeId::Forge),
+//!         ];
+//!     }
+//!     fn on_step(&mut self) {
+//!         self.chrono_boost();
+//!     }
+//! }
+//! ```
+
+use crate::{
+	action::Target,
+	bot::Bot,
+	game_data::GameData,
+	ids::{AbilityId, BuffId, UnitTypeId},
+	unit::Unit,
+};
+use rustc_hash::FxHashMap;
+
+/// Buff structures get from chrono boost.
+pub const CHRONO_BUFF: BuffId = BuffId::ChronoBoostEnergyCost;
+/// Duration of chrono boost in game loops.
+pub const CHRONO_DURATION: u32 = 448;
+/// Production speed multiplier of boosted structures.
+pub const CHRONO_SPEED: f32 = 1.5;
+/// Energy cost of chrono boost.
+const CHRONO_COST: u32 = 50;
+
+/// Kind of structures chrono boost is used on.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ChronoTarget {
+	/// Nexuses training probes, while workers are below [`worker_limit`](ChronoScheduler::worker_limit).
+	Workers,
+	/// Structures researching upgrades, except warp gate.
+	Upgrades,
+	/// Cybernetics core researching warp gate.
+	WarpGate,
+	/// Structures training units, except probes.
+	Units,
+	/// Structures of given type with any order.
+	Structure(UnitTypeId),
+	/// Structures using given ability.
+	Ability(AbilityId),
+}
+impl ChronoTarget {
+	/// Checks if given structure with given order fits the target.
+	fn fits(self, game_data: &GameData, structure: &Unit, ability: AbilityId) -> bool {
+		match self {
+			ChronoTarget::Workers => ability == AbilityId::NexusTrainProbe,
+			ChronoTarget::Upgrades => {
+				ability != AbilityId::ResearchWarpGate
+					&& game_data.upgrades.values().any(|u| u.ability == ability)
+			}
+			ChronoTarget::WarpGate => ability == AbilityId::ResearchWarpGate,
+			ChronoTarget::Units => {
+				ability != AbilityId::NexusTrainProbe
+					&& game_data.units.values().any(|u| u.ability == Some(ability))
+			}
+			ChronoTarget::Structure(id) => structure.type_id() == id,
+			ChronoTarget::Ability(id) => ability == id,
+		}
+	}
+}
+
+/// Settings and state of chrono boost scheduling.
+#[derive(Debug, Clone)]
+pub struct ChronoScheduler {
+	/// Targets in order of priority. [Default: `[Workers, Upgrades, WarpGate]`]
+	pub priority: Vec<ChronoTarget>,
+	/// Probes are boosted only while amount of workers is below this limit. [Default: `44`]
+	pub worker_limit: u32,
+	/// Energy nexuses keep for other abilities besides chrono cost. [Default: `0`]
+	pub energy_reserve: u32,
+	pending: FxHashMap<u64, u32>,
+	game_loop: u32,
+}
+impl Default for ChronoScheduler {
+	fn default() -> Self {
+		Self {
+			priority: vec![
+				ChronoTarget::Workers,
+				ChronoTarget::Upgrades,
+				ChronoTarget::WarpGate,
+			],
+			worker_limit: 44,
+			energy_reserve: 0,
+			pending: Default::default(),
+			game_loop: 0,
+		}
+	}
+}
+impl ChronoScheduler {
+	/// Checks if structure with given tag was ordered to be boosted, but doesn't have buff yet.
+	pub fn is_pending(&self, tag: u64) -> bool {
+		self.pending.contains_key(&tag)
+	}
+
+	pub(crate) fn update(&mut self, bot: &Bot) {
+		let game_loop = bot.state.observation.game_loop();
+		self.game_loop = game_loop;
+
+		let structures = &bot.units.my.structures;
+		// Nexus has to reach target first, so pending boost is discarded if it's not applied in time
+		self.pending.retain(|tag, ordered| {
+			game_loop - *ordered < 22 && matches!(structures.get(*tag), Some(s) if !s.has_buff(CHRONO_BUFF))
+		});
+	}
+}
+
+impl Bot {
+	/// Ready nexuses with their energy.
+	pub fn nexus_energy(&self) -> Vec<(u64, u32)> {
+		self.units
+			.my
+			.townhalls
+			.iter()
+			.filter(|u| u.type_id() == UnitTypeId::Nexus && u.is_ready())
+			.filter_map(|u| Some((u.tag(), u.energy()?)))
+			.collect()
+	}
+	/// Checks if structure is boosted now or was ordered to be boosted.
+	pub fn is_chrono_boosted(&self, structure: &Unit) -> bool {
+		structure.has_buff(CHRONO_BUFF) || self.chrono.is_pending(structure.tag())
+	}
+
+	/// Orders nexuses with enough energy to boost structures chosen by priority.
+	///
+	/// Structures already boosted, pending boost or idle are never chosen.
+	pub fn chrono_boost(&mut self) {
+		let reserve = self.chrono.energy_reserve;
+		let mut nexuses = self
+			.units
+			.my
+			.townhalls
+			.filter(|u| {
+				u.type_id() == UnitTypeId::Nexus
+					&& u.is_ready() && u.can_cast(AbilityId::EffectChronoBoostEnergyCost)
+					&& matches!(u.energy(), Some(energy) if energy >= CHRONO_COST + reserve)
+			})
+			.into_iter()
+			.collect::<Vec<_>>();
+		if nexuses.is_empty() {
+			return;
+		}
+
+		let workers_allowed = self.supply_workers < self.chrono.worker_limit;
+		let candidates = self
+			.units
+			.my
+			.structures
+			.filter(|s| s.is_ready() && !s.is_idle() && !self.is_chrono_boosted(s));
+
+		let mut boosted = Vec::new();
+		for target in self.chrono.priority.clone() {
+			if target == ChronoTarget::Workers && !workers_allowed {
+				continue;
+			}
+			for structure in &candidates {
+				if boosted.contains(&structure.tag()) {
+					continue;
+				}
+				let ability = match structure.ordered_ability() {
+					Some(ability) => ability,
+					None => continue,
+				};
+				if !target.fits(&self.game_data, structure, ability) {
+					continue;
+				}
+				let nexus = match nexuses.pop() {
+					Some(nexus) => nexus,
+					None => break,
+				};
+				nexus.command(
+					AbilityId::EffectChronoBoostEnergyCost,
+					Target::Tag(structure.tag()),
+					false,
+				);
+				boosted.push(structure.tag());
+			}
+		}
+
+		let game_loop = self.state.observation.game_loop();
+		for tag in boosted {
+			self.chrono.pending.insert(tag, game_loop);
+		}
+	}
+
+	/// Estimates game loops left until current order of given structure is finished,
+	/// considering active and pending chrono boosts.
+	///
+	/// Returns `None` if structure is idle or its order isn't training or research.
+	pub fn production_eta(&self, structure: &Unit) -> Option<f32> {
+		let order = structure.orders().first()?;
+		let duration = self
+			.game_data
+			.units
+			.values()
+			.find(|u| u.ability == Some(order.ability))
+			.map(|u| u.build_time)
+			.or_else(|| {
+				self.game_data
+					.upgrades
+					.values()
+					.find(|u| u.ability == order.ability)
+					.map(|u| u.research_time)
+			})?;
+		let work = (1.0 - order.progress) * duration;
+
+		let boost = if structure.has_buff(CHRONO_BUFF) {
+			structure.buff_duration_remain().unwrap_or(CHRONO_DURATION)
+		} else if self.chrono.is_pending(structure.tag()) {
+			CHRONO_DURATION
+		} else {
+			0
+		} as f32;
+
+		Some(if work <= boost * CHRONO_SPEED {
+			work / CHRONO_SPEED
+		} else {
+			boost + (work - boost * CHRONO_SPEED)
+		})
+	}
+}
@@ -283,9 +283,10 @@ where
 		};
 		join_game2(&self.human_settings, human_api, Some(&ports))?;
 		join_game2(&bot_settings, self.bot.api(), Some(&ports))?;
-		let _ = wait_join(human_api)?;
+		let human_id = wait_join(human_api)?;
 		let player_id = wait_join(self.bot.api())?;
 		self.bot.player_id = player_id;
+		self.bot.console.human_players.push(human_id);
 
 		set_static_data(self.bot)?;
 
@@ -0,0 +1,511 @@
+//! Console for live debugging through in-game chat.
+//!
+//! Bot keeps [`Console`] in [`console`](crate::bot::Bot::console) field.
+//! [`Bot::run_console`] parses chat messages starting with [`prefix`](Console::prefix)
+//! into registered commands, runs their handlers and sends responses back to chat.
+//! Commands without handlers are returned, so they can be handled by your bot.
+//! Only messages from [allowed players](Console::allowed_players) are parsed.
+//!
+//! Built-in commands:
+//! - `help` — lists all commands.
+//! - `overlay <name>` — toggles [debug overlay](crate::debug::Overlay) with given name.
+//! - `step <loops>` — changes [`game_step`](crate::bot::Bot::game_step).
+//! - `info <tag>` — dumps info about unit with given tag.
+//! - `spawn <type> [count] [player]` — creates units at camera position (at most 50 at once).
+//! - `strategy [name]` — switches to one of [registered strategies](Console::add_strategy).
+//!
+//! ```
+//! use rust_sc2::{console::*, prelude::*};
+//!
+//! #[bot]
+//! #[derive(Default)]
+//! struct MyBot;
+//! impl MyBot {
+//!     fn on_start(&mut self) {
+//!         self.console.add_strategy("macro");
+//!         self.console.add_strategy("rush");
+//!         self.console.register(
+//!             ConsoleCommand::new("minerals", "Prints current minerals", Some(|bot, _| {
+//!                 Ok(format!("Minerals: {}", bot.minerals))
+//!             })),
+//!         );
+//!         self.console.register(
+//!             ConsoleCommand::new("attack", "Attacks given expansion", None).arg("index", ArgType::Int),
+//!         );
+//!     }
+//!     fn on_step(&mut self) {
+//!         for command in self.run_console() {
+//!             if command.name == "attack" {
+//!                 let index = command.args.int(0).unwrap_or(0);
+//!                 /* Attack */
+//!             }
+//!         }
+//!         if self.console.strategy() == Some("rush") {
+//!             /* Rush */
+//!         }
+//!     }
+//! }
+//! ```
+
+use crate::{bot::Bot, debug::Overlay, geometry::Point2, ids::UnitTypeId};
+use num_traits::FromPrimitive;
+use rustc_hash::FxHashMap;
+use std::fmt::Write;
+
+/// Maximum amount of units created with single `spawn` command.
+const MAX_SPAWN_COUNT: i64 = 50;
+
+lazy_static! {
+	static ref UNIT_TYPES_BY_NAME: FxHashMap<String, UnitTypeId> = (0..=u16::MAX as u32)
+		.filter_map(UnitTypeId::from_u32)
+		.map(|id| (format!("{:?}", id).to_lowercase(), id))
+		.collect();
+}
+
+/// Handler of console command, returns response or error sent back to chat.
+pub type CommandHandler = fn(&mut Bot, &Args) -> Result<String, String>;
+
+/// Type of command's argument.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ArgType {
+	/// Integer number.
+	Int,
+	/// Floating point number.
+	Float,
+	/// `true`/`false`, `on`/`off` or `1`/`0`.
+	Bool,
+	/// Single word.
+	Text,
+	/// Unit type by name (case insensitive) or numeric id.
+	UnitType,
+}
+impl ArgType {
+	fn parse(self, s: &str) -> Option<ArgValue> {
+		Some(match self {
+			ArgType::Int => ArgValue::Int(s.parse().ok()?),
+			ArgType::Float => ArgValue::Float(s.parse().ok()?),
+			ArgType::Bool => ArgValue::Bool(match s.to_lowercase().as_str() {
+				"true" | "on" | "1" => true,
+				"false" | "off" | "0" => false,
+				_ => return None,
+			}),
+			ArgType::Text => ArgValue::Text(s.to_string()),
+			ArgType::UnitType => ArgValue::UnitType(parse_unit_type(s)?),
+		})
+	}
+}
+
+fn parse_unit_type(s: &str) -> Option<UnitTypeId> {
+	if let Ok(id) = s.parse::<u32>() {
+		return UnitTypeId::from_u32(id);
+	}
+	UNIT_TYPES_BY_NAME.get(&s.to_lowercase()).copied()
+}
+
+/// Parsed value of command's argument.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArgValue {
+	#[allow(missing_docs)]
+	Int(i64),
+	#[allow(missing_docs)]
+	Float(f32),
+	#[allow(missing_docs)]
+	Bool(bool),
+	#[allow(missing_docs)]
+	Text(String),
+	#[allow(missing_docs)]
+	UnitType(UnitTypeId),
+}
+
+/// Parsed arguments of command. Optional arguments which weren't given are missing.
+#[derive(Debug, Default, Clone)]
+pub struct Args(Vec<ArgValue>);
+impl Args {
+	/// Returns argument on given index.
+	pub fn get(&self, index: usize) -> Option<&ArgValue> {
+		self.0.get(index)
+	}
+	/// Returns amount of given arguments.
+	pub fn len(&self) -> usize {
+		self.0.len()
+	}
+	/// Checks if no arguments were given.
+	pub fn is_empty(&self) -> bool {
+		self.0.is_empty()
+	}
+	/// Returns integer argument on given index.
+	pub fn int(&self, index: usize) -> Option<i64> {
+		match self.0.get(index)? {
+			ArgValue::Int(value) => Some(*value),
+			_ => None,
+		}
+	}
+	/// Returns floating point argument on given index.
+	pub fn float(&self, index: usize) -> Option<f32> {
+		match self.0.get(index)? {
+			ArgValue::Float(value) => Some(*value),
+			ArgValue::Int(value) => Some(*value as f32),
+			_ => None,
+		}
+	}
+	/// Returns boolean argument on given index.
+	pub fn bool(&self, index: usize) -> Option<bool> {
+		match self.0.get(index)? {
+			ArgValue::Bool(value) => Some(*value),
+			_ => None,
+		}
+	}
+	/// Returns text argument on given index.
+	pub fn text(&self, index: usize) -> Option<&str> {
+		match self.0.get(index)? {
+			ArgValue::Text(value) => Some(value),
+			_ => None,
+		}
+	}
+	/// Returns unit type argument on given index.
+	pub fn unit_type(&self, index: usize) -> Option<UnitTypeId> {
+		match self.0.get(index)? {
+			ArgValue::UnitType(value) => Some(*value),
+			_ => None,
+		}
+	}
+}
+
+/// Command which can be run from chat.
+#[derive(Clone)]
+pub struct ConsoleCommand {
+	/// Name of command, typed after prefix.
+	pub name: String,
+	/// Description shown in `help`.
+	pub help: String,
+	args: Vec<(String, ArgType, bool)>,
+	handler: Option<CommandHandler>,
+}
+impl ConsoleCommand {
+	/// Constructs new command with given name, description and handler.
+	/// Commands without handler are returned from [`Bot::run_console`].
+	pub fn new<S1, S2>(name: S1, help: S2, handler: Option<CommandHandler>) -> Self
+	where
+		S1: Into<String>,
+		S2: Into<String>,
+	{
+		Self {
+			name: name.into(),
+			help: help.into(),
+			args: Vec::new(),
+			handler,
+		}
+	}
+	/// Adds required argument to command.
+	pub fn arg<S: Into<String>>(mut self, name: S, kind: ArgType) -> Self {
+		self.args.push((name.into(), kind, false));
+		self
+	}
+	/// Adds optional argument to command. Optional arguments should go after required ones.
+	pub fn optional_arg<S: Into<String>>(mut self, name: S, kind: ArgType) -> Self {
+		self.args.push((name.into(), kind, true));
+		self
+	}
+	/// Returns usage of command, e.g. `spawn <type> [count]`.
+	pub fn usage(&self) -> String {
+		let mut usage = self.name.clone();
+		for (name, _, optional) in &self.args {
+			if *optional {
+				let _ = write!(usage, " [{}]", name);
+			} else {
+				let _ = write!(usage, " <{}>", name);
+			}
+		}
+		usage
+	}
+
+	fn parse_args(&self, words: &[&str]) -> Result<Args, String> {
+		if words.len() > self.args.len() {
+			return Err(format!("Too many arguments. Usage: {}", self.usage()));
+		}
+		let mut args = Vec::new();
+		for (i, (name, kind, optional)) in self.args.iter().enumerate() {
+			match words.get(i) {
+				Some(word) => args.push(
+					kind.parse(word)
+						.ok_or_else(|| format!("Invalid {} \"{}\". Usage: {}", name, word, self.usage()))?,
+				),
+				None if *optional => break,
+				None => return Err(format!("Missing {}. Usage: {}", name, self.usage())),
+			}
+		}
+		Ok(Args(args))
+	}
+}
+
+/// Command from chat, which was registered without handler.
+#[derive(Debug, Clone)]
+pub struct Invocation {
+	/// Id of player who sent the command.
+	pub player_id: u32,
+	/// Name of command.
+	pub name: String,
+	/// Parsed arguments.
+	pub args: Args,
+}
+
+/// Parser of chat commands.
+#[derive(Clone)]
+pub struct Console {
+	/// Only messages starting with this prefix are considered commands. [Default: `"!"`]
+	pub prefix: String,
+	/// Send responses of commands to chat. [Default: `true`]
+	pub respond: bool,
+	/// Ids of players whose messages are parsed.
+	/// `None` allows only bot itself and human opponent in games vs human. [Default: `None`]
+	pub allowed_players: Option<Vec<u32>>,
+	pub(crate) human_players: Vec<u32>,
+	commands: Vec<ConsoleCommand>,
+	strategies: Vec<String>,
+	strategy: Option<String>,
+}
+impl Default for Console {
+	fn default() -> Self {
+		Self {
+			prefix: "!".to_string(),
+			respond: true,
+			allowed_players: None,
+			human_players: Vec::new(),
+			commands: builtin_commands(),
+			strategies: Vec::new(),
+			strategy: None,
+		}
+	}
+}
+impl Console {
+	/// Registers command, replacing previously registered one with the same name (including built-ins).
+	pub fn register(&mut self, command: ConsoleCommand) {
+		self.commands.retain(|c| c.name != command.name);
+		self.commands.push(command);
+	}
+	/// Removes command with given name.
+	pub fn unregister(&mut self, name: &str) {
+		self.commands.retain(|c| c.name != name);
+	}
+	/// All registered commands.
+	pub fn commands(&self) -> &[ConsoleCommand] {
+		&self.commands
+	}
+
+	/// Adds strategy, which can be chosen with `strategy` command.
+	/// First added strategy becomes current one.
+	pub fn add_strategy<S: Into<String>>(&mut self, name: S) {
+		let name = name.into();
+		if self.strategy.is_none() {
+			self.strategy = Some(name.clone());
+		}
+		if !self.strategies.contains(&name) {
+			self.strategies.push(name);
+		}
+	}
+	/// All added strategies.
+	pub fn strategies(&self) -> &[String] {
+		&self.strategies
+	}
+	/// Current strategy.
+	pub fn strategy(&self) -> Option<&str> {
+		self.strategy.as_deref()
+	}
+	/// Switches to given strategy, returns `false` if it wasn't added.
+	pub fn set_strategy(&mut self, name: &str) -> bool {
+		if self.strategies.iter().any(|s| s == name) {
+			self.strategy = Some(name.to_string());
+			true
+		} else {
+			false
+		}
+	}
+
+	/// Checks if messages of given player are parsed, `bot_id` is in-game id of the bot.
+	pub fn is_allowed(&self, player_id: u32, bot_id: u32) -> bool {
+		match &self.allowed_players {
+			Some(players) => players.contains(&player_id),
+			None => player_id == bot_id || self.human_players.contains(&player_id),
+		}
+	}
+
+	/// Parses message into command and its arguments.
+	///
+	/// Returns `None` if message isn't a command,
+	/// and `Some(Err(..))` if command is unknown or its arguments are invalid.
+	pub fn parse(&self, message: &str) -> Option<Result<(&ConsoleCommand, Args), String>> {
+		let message = message.trim().strip_prefix(self.prefix.as_str())?;
+		let mut words = message.split_whitespace();
+		let name = words.next()?;
+		let words = words.collect::<Vec<_>>();
+
+		Some(
+			match self.commands.iter().find(|c| c.name.eq_ignore_ascii_case(name)) {
+				Some(command) => command.parse_args(&words).map(|args| (command, args)),
+				None => Err(format!("Unknown command \"{}\", try {}help", name, self.prefix)),
+			},
+		)
+	}
+}
+
+fn builtin_commands() -> Vec<ConsoleCommand> {
+	vec![
+		ConsoleCommand::new("help", "Lists all commands", Some(help)),
+		ConsoleCommand::new("overlay", "Toggles debug overlay", Some(overlay)).arg("name", ArgType::Text),
+		ConsoleCommand::new("step", "Changes game step", Some(step)).arg("loops", ArgType::Int),
+		ConsoleCommand::new("info", "Dumps info about unit", Some(info)).arg("tag", ArgType::Int),
+		ConsoleCommand::new("spawn", "Creates units at camera position", Some(spawn))
+			.arg("type", ArgType::UnitType)
+			.optional_arg("count", ArgType::Int)
+			.optional_arg("player", ArgType::Int),
+		ConsoleCommand::new("strategy", "Switches strategy", Some(strategy))
+			.optional_arg("name", ArgType::Text),
+	]
+}
+
+fn help(bot: &mut Bot, _args: &Args) -> Result<String, String> {
+	// Lines don't start with prefix, so echo of response isn't parsed as command
+	let mut help = format!("Commands (type with \"{}\"):", bot.console.prefix);
+	for c in &bot.console.commands {
+		let _ = write!(help, "\n{} - {}", c.usage(), c.help);
+	}
+	Ok(help)
+}
+fn overlay(bot: &mut Bot, args: &Args) -> Result<String, String> {
+	let name = args.text(0).unwrap_or_default();
+	match bot.debug.toggle_overlay(name) {
+		Some(enabled) => Ok(format!(
+			"Overlay {} {}",
+			name,
+			if enabled { "enabled" } else { "disabled" }
+		)),
+		None => Err(format!(
+			"Unknown overlay \"{}\", available: {}",
+			name,
+			Overlay::ALL
+				.iter()
+				.map(|o| o.name())
+				.collect::<Vec<_>>()
+				.join(", ")
+		)),
+	}
+}
+fn step(bot: &mut Bot, args: &Args) -> Result<String, String> {
+	match args.int(0) {
+		Some(loops) if loops > 0 => {
+			bot.set_game_step(loops as u32);
+			Ok(format!("Game step set to {}", loops))
+		}
+		_ => Err("Game step should be positive".to_string()),
+	}
+}
+fn info(bot: &mut Bot, args: &Args) -> Result<String, String> {
+	let tag = args.int(0).unwrap_or_default() as u64;
+	let u = bot
+		.units
+		.all
+		.get(tag)
+		.ok_or_else(|| format!("Unit {} not found", tag))?;
+
+	let mut info = format!(
+		"{:?} [{}] {:?} at ({:.1}, {:.1})",
+		u.type_id(),
+		tag,
+		u.alliance(),
+		u.position().x,
+		u.position().y
+	);
+	if let (Some(health), Some(health_max)) = (u.health(), u.health_max()) {
+		let _ = write!(info, " HP: {}/{}", health, health_max);
+	}
+	if let (Some(shield), Some(shield_max)) = (u.shield(), u.shield_max()) {
+		let _ = write!(info, " SP: {}/{}", shield, shield_max);
+	}
+	if let Some(energy) = u.energy() {
+		let _ = write!(info, " EP: {}", energy);
+	}
+	for order in u.orders() {
+		let _ = write!(
+			info,
+			"\n{:?} -> {:?} ({:.0}%)",
+			order.ability,
+			order.target,
+			order.progress * 100.0
+		);
+	}
+	Ok(info)
+}
+fn spawn(bot: &mut Bot, args: &Args) -> Result<String, String> {
+	let unit = args.unit_type(0).ok_or("Unit type required")?;
+	let count = args.int(1).unwrap_or(1).clamp(1, MAX_SPAWN_COUNT) as u32;
+	let owner = args.int(2).map_or(bot.player_id, |player| player as u32);
+	let pos: Point2 = bot.state.observation.raw.camera;
+
+	bot.debug.create_units(&[(unit, Some(owner), pos, count)]);
+	Ok(format!("Spawned {} {:?} for player {}", count, unit, owner))
+}
+fn strategy(bot: &mut Bot, args: &Args) -> Result<String, String> {
+	let console = &mut bot.console;
+	match args.text(0) {
+		Some(name) if console.set_strategy(name) => Ok(format!("Strategy switched to {}", name)),
+		Some(name) => Err(format!(
+			"Unknown strategy \"{}\", available: {}",
+			name,
+			console.strategies.join(", ")
+		)),
+		None => Ok(format!(
+			"Current strategy: {}, available: {}",
+			console.strategy.as_deref().unwrap_or("none"),
+			console.strategies.join(", ")
+		)),
+	}
+}
+
+impl Bot {
+	/// Parses commands from chat messages of [allowed players](Console::allowed_players) on this step,
+	/// runs their handlers and sends responses to chat (if [`respond`](Console::respond) is enabled).
+	///
+	/// Returns invocations of commands registered without handlers.
+	pub fn run_console(&mut self) -> Vec<Invocation> {
+		let messages = self
+			.state
+			.chat
+			.iter()
+			.filter(|m| {
+				self.console.is_allowed(m.player_id, self.player_id)
+					&& m.message.trim().starts_with(self.console.prefix.as_str())
+			})
+			.map(|m| (m.player_id, m.message.clone()))
+			.collect::<Vec<_>>();
+
+		let mut invocations = Vec::new();
+		for (player_id, message) in messages {
+			let response = match self.console.parse(&message) {
+				Some(Ok((command, args))) => match command.handler {
+					Some(handler) => handler(self, &args),
+					None => {
+						invocations.push(Invocation {
+							player_id,
+							name: command.name.clone(),
+							args,
+						});
+						continue;
+					}
+				},
+				Some(Err(e)) => Err(e),
+				None => continue,
+			};
+			if self.console.respond {
+				match response {
+					Ok(text) => {
+						for line in text.lines() {
+							self.chat(line);
+						}
+					}
+					Err(e) => self.chat(&format!("Error: {}", e)),
+				}
+			}
+		}
+		invocations
+	}
+}
@@ -869,6 +869,89 @@ lazy_static! {
 		UnitTypeId::InhibitorZoneFlyingMedium => 5.0,
 		UnitTypeId::InhibitorZoneFlyingLarge => 6.0,
 	];
+	/// Cooldowns of abilities in game loops, since they're not provided by the API.
+	pub static ref ABILITY_COOLDOWNS: HashMap<AbilityId, u32> = hashmap![
+		AbilityId::EffectBlink => 157,
+		AbilityId::EffectBlinkStalker => 157,
+		AbilityId::EffectShadowStride => 314,
+		AbilityId::EffectStim => 22,
+		AbilityId::EffectStimMarine => 22,
+		AbilityId::EffectStimMarineRedirect => 22,
+		AbilityId::EffectStimMarauder => 22,
+		AbilityId::EffectStimMarauderRedirect => 22,
+		AbilityId::TransfusionTransfusion => 22,
+		AbilityId::EffectChronoBoostEnergyCost => 22,
+		AbilityId::EffectPurificationNova => 479,
+		AbilityId::EffectCorrosiveBile => 157,
+		AbilityId::KD8ChargeKD8Charge => 314,
+		AbilityId::YamatoYamatoGun => 1590,
+		AbilityId::EffectTacticalJump => 1590,
+		AbilityId::AdeptPhaseShiftAdeptPhaseShift => 246,
+		AbilityId::EffectVoidRayPrismaticAlignment => 963,
+		AbilityId::EffectMassRecallNexus => 2912,
+		AbilityId::EffectMedivacIgniteAfterburners => 314,
+		AbilityId::EffectSpawnLocusts => 963,
+		AbilityId::BatteryOverchargeBatteryOvercharge => 1344,
+	];
+	/// Energy costs of abilities, since they're not provided by the API.
+	pub static ref ABILITY_ENERGY_COSTS: HashMap<AbilityId, u32> = hashmap![
+		AbilityId::FungalGrowthFungalGrowth => 75,
+		AbilityId::GuardianShieldGuardianShield => 75,
+		AbilityId::FeedbackFeedback => 50,
+		AbilityId::HallucinationArchon => 75,
+		AbilityId::HallucinationColossus => 75,
+		AbilityId::HallucinationHighTemplar => 75,
+		AbilityId::HallucinationImmortal => 75,
+		AbilityId::HallucinationPhoenix => 75,
+		AbilityId::HallucinationProbe => 75,
+		AbilityId::HallucinationStalker => 75,
+		AbilityId::HallucinationVoidRay => 75,
+		AbilityId::HallucinationWarpPrism => 75,
+		AbilityId::HallucinationZealot => 75,
+		AbilityId::HallucinationOracle => 75,
+		AbilityId::HallucinationDisruptor => 75,
+		AbilityId::HallucinationAdept => 75,
+		AbilityId::CalldownMULECalldownMULE => 50,
+		AbilityId::SupplyDropSupplyDrop => 50,
+		AbilityId::ScannerSweepScan => 50,
+		AbilityId::NeuralParasiteNeuralParasite => 100,
+		AbilityId::EffectInjectLarva => 25,
+		AbilityId::BuildCreepTumorQueen => 25,
+		AbilityId::TransfusionTransfusion => 50,
+		AbilityId::EffectChronoBoostEnergyCost => 50,
+		AbilityId::BehaviorCloakOnGhost => 25,
+		AbilityId::BehaviorCloakOnBanshee => 25,
+		AbilityId::SnipeSnipe => 50,
+		AbilityId::EffectGhostSnipe => 50,
+		AbilityId::EMPEMP => 75,
+		AbilityId::PsiStormPsiStorm => 75,
+		AbilityId::ForceFieldForceField => 50,
+		AbilityId::BlindingCloudBlindingCloud => 100,
+		AbilityId::EffectAbduct => 75,
+		AbilityId::ParasiticBombParasiticBomb => 125,
+		AbilityId::OracleRevelationOracleRevelation => 25,
+		AbilityId::BehaviorPulsarBeamOn => 25,
+		AbilityId::BuildStasisTrap => 50,
+		AbilityId::EffectTimeWarp => 100,
+		AbilityId::EffectInterferenceMatrix => 75,
+		AbilityId::EffectAntiArmorMissile => 75,
+		AbilityId::BuildAutoTurretAutoTurret => 50,
+		AbilityId::AmorphousArmorcloudAmorphousArmorcloud => 75,
+	];
+	/// Units hatched from eggs mapped to abilities used by larva: (unit type, amount).
+	pub static ref LARVA_UNITS: HashMap<AbilityId, (UnitTypeId, usize)> = hashmap![
+		AbilityId::LarvaTrainDrone => (UnitTypeId::Drone, 1),
+		AbilityId::LarvaTrainZergling => (UnitTypeId::Zergling, 2),
+		AbilityId::LarvaTrainOverlord => (UnitTypeId::Overlord, 1),
+		AbilityId::LarvaTrainHydralisk => (UnitTypeId::Hydralisk, 1),
+		AbilityId::LarvaTrainMutalisk => (UnitTypeId::Mutalisk, 1),
+		AbilityId::LarvaTrainUltralisk => (UnitTypeId::Ultralisk, 1),
+		AbilityId::LarvaTrainRoach => (UnitTypeId::Roach, 1),
+		AbilityId::LarvaTrainInfestor => (UnitTypeId::Infestor, 1),
+		AbilityId::LarvaTrainCorruptor => (UnitTypeId::Corruptor, 1),
+		AbilityId::LarvaTrainViper => (UnitTypeId::Viper, 1),
+		AbilityId::TrainSwarmHost => (UnitTypeId::SwarmHostMP, 1),
+	];
 	pub(crate) static ref SPEED_BUFFS: HashMap<BuffId, f32> = hashmap![
 		BuffId::Stimpack => 1.5,
 		BuffId::StimpackMarauder => 1.5,
@@ -0,0 +1,265 @@
+//! Planner of zerg's creep spread.
+//!
+//! Bot keeps [`CreepPlanner`] in [`creep_planner`](crate::bot::Bot::creep_planner) field,
+//! which tracks own creep tumors and creep coverage of the map.
+//! Tumors and queens are ordered to spread creep with [`Bot::spread_creep`]:
+//! ```
+//! use rust_sc2::{creep::CreepDirection, prelude::*};
+//!
+//! #[bot]
+//! #[derive(Default)]
+//! struct MyBot;
+//! impl MyBot {
+//!     fn on_start(&mut self) {
+//!         self.creep_planner.directions = vec![CreepDirection::Enemy, CreepDirection::Bases];
+//!     }
+//!     fn on_step(&mut self) {
+//!         let queens = self.units.my.units.of_type(UnitTypeId::Queen);
+//!         self.spread_creep(&queens);
+//!     }
+//! }
+//! ```
+
+use crate::{
+	action::Target,
+	bot::{Bot, Locked},
+	distance::*,
+	geometry::Point2,
+	ids::{AbilityId, UnitTypeId},
+	units::Units,
+};
+use rustc_hash::FxHashMap;
+use std::mem;
+
+const TUMOR_TYPES: [UnitTypeId; 3] = [
+	UnitTypeId::CreepTumor,
+	UnitTypeId::CreepTumorQueen,
+	UnitTypeId::CreepTumorBurrowed,
+];
+
+/// Direction creep is spread to.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum CreepDirection {
+	/// Towards enemy start location.
+	Enemy,
+	/// Between own bases.
+	Bases,
+	/// Towards given position.
+	Pos(Point2),
+}
+
+/// State of creep tumor.
+#[variant_checkers]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum TumorState {
+	/// Tumor is spawning or its spread ability is on cooldown.
+	Pending,
+	/// Tumor can spawn new tumor.
+	Active,
+	/// Tumor already spawned new tumor and can't spread creep anymore.
+	Used,
+}
+
+/// Tracker of creep tumors and creep coverage.
+#[derive(Debug, Clone)]
+pub struct CreepPlanner {
+	/// Directions creep is spread to. [Default: `[Enemy]`]
+	pub directions: Vec<CreepDirection>,
+	/// Maximum distance from tumor to new tumor. [Default: `10`]
+	pub tumor_range: f32,
+	/// Maximum distance from queen to placed tumor. [Default: `8`]
+	pub queen_range: f32,
+	/// Energy queens keep for other abilities besides tumor cost. [Default: `0`]
+	pub queen_energy_reserve: u32,
+	/// Game loops between records of creep coverage history. [Default: `224`]
+	pub history_interval: u32,
+	tumors: FxHashMap<u64, TumorState>,
+	coverage: f32,
+	history: Vec<(u32, f32)>,
+}
+impl Default for CreepPlanner {
+	fn default() -> Self {
+		Self {
+			directions: vec![CreepDirection::Enemy],
+			tumor_range: 10.0,
+			queen_range: 8.0,
+			queen_energy_reserve: 0,
+			history_interval: 224,
+			tumors: Default::default(),
+			coverage: 0.0,
+			history: Vec::new(),
+		}
+	}
+}
+impl CreepPlanner {
+	/// Returns state of creep tumor with given tag.
+	pub fn tumor_state(&self, tag: u64) -> Option<TumorState> {
+		self.tumors.get(&tag).copied()
+	}
+	/// Returns tags of tumors in given state.
+	pub fn tumors(&self, state: TumorState) -> impl Iterator<Item = u64> + '_ {
+		self.tumors
+			.iter()
+			.filter(move |(_, s)| **s == state)
+			.map(|(tag, _)| *tag)
+	}
+	/// Amount of own creep tumors.
+	pub fn tumors_count(&self) -> usize {
+		self.tumors.len()
+	}
+	/// Part of pathable map covered with creep, last measured on history record.
+	pub fn coverage(&self) -> f32 {
+		self.coverage
+	}
+	/// Records of creep coverage: (game loop, coverage).
+	pub fn history(&self) -> &[(u32, f32)] {
+		&self.history
+	}
+
+	fn mark_used(&mut self, tag: u64) {
+		self.tumors.insert(tag, TumorState::Used);
+	}
+
+	pub(crate) fn update(&mut self, bot: &Bot) {
+		let game_loop = bot.state.observation.game_loop();
+
+		let old = mem::take(&mut self.tumors);
+		for u in &bot.units.my.structures.of_types(&&TUMOR_TYPES[..]) {
+			let state = if u.has_ability(AbilityId::BuildCreepTumorTumor) {
+				TumorState::Active
+			} else {
+				match old.get(&u.tag()) {
+					Some(TumorState::Active) | Some(TumorState::Used) => TumorState::Used,
+					_ => TumorState::Pending,
+				}
+			};
+			self.tumors.insert(u.tag(), state);
+		}
+
+		if matches!(self.history.last(), Some((last, _)) if game_loop < last + self.history_interval) {
+			return;
+		}
+		let creep = bot.state.observation.raw.creep.read_lock();
+		let (mut pathable, mut covered) = (0_usize, 0_usize);
+		for ((x, y), pixel) in bot.game_info.pathing_grid.indexed_iter() {
+			if pixel.is_empty() {
+				pathable += 1;
+				if matches!(creep.get((x, y)), Some(p) if p.is_empty()) {
+					covered += 1;
+				}
+			}
+		}
+		self.coverage = if pathable > 0 {
+			covered as f32 / pathable as f32
+		} else {
+			0.0
+		};
+		self.history.push((game_loop, self.coverage));
+	}
+}
+
+impl Bot {
+	/// Returns the best position for new creep tumor within given range from given position.
+	///
+	/// Position should be visible and have creep. Positions making progress along
+	/// [`directions`](CreepPlanner::directions) and far from other tumors are preferred,
+	/// while spots used by townhalls on expansions are avoided.
+	pub fn creep_tumor_position(&self, from: Point2, range: f32) -> Option<Point2> {
+		self.tumor_position(from, range, &[])
+	}
+
+	fn tumor_position(&self, from: Point2, range: f32, planned: &[Point2]) -> Option<Point2> {
+		let targets = self
+			.creep_planner
+			.directions
+			.iter()
+			.flat_map(|d| match d {
+				CreepDirection::Enemy => vec![self.enemy_start],
+				CreepDirection::Bases => self.owned_expansions().map(|exp| exp.loc).collect(),
+				CreepDirection::Pos(pos) => vec![*pos],
+			})
+			.filter(|t| !self.has_creep(*t))
+			.collect::<Vec<_>>();
+		let tumors = self
+			.units
+			.my
+			.structures
+			.of_types(&&TUMOR_TYPES[..])
+			.iter()
+			.map(|u| u.position())
+			.chain(planned.iter().copied())
+			.collect::<Vec<_>>();
+		let obstacles = self
+			.units
+			.all
+			.filter(|u| u.is_structure() && u.is_closer(range + u.radius() + 1.0, from));
+
+		let r = range as isize;
+		let center = from.floor();
+		let score = |p: Point2| {
+			let progress = targets
+				.iter()
+				.map(|t| from.distance(*t) - p.distance(*t))
+				.max_by(|a, b| a.partial_cmp(b).unwrap())
+				.unwrap_or(0.0);
+			let spread = tumors.iter().map(|t| t.distance(p)).fold(range, f32::min);
+			progress + spread
+		};
+
+		(-r..=r)
+			.flat_map(|dx| (-r..=r).map(move |dy| center.offset(dx as f32 + 0.5, dy as f32 + 0.5)))
+			.filter(|p| {
+				from.is_closer(range, *p)
+					&& self.has_creep(*p)
+					&& self.is_visible(*p)
+					&& self.is_pathable(*p)
+					&& !obstacles.iter().any(|u| u.is_closer(u.radius() + 1.0, *p))
+					&& !self.expansions.iter().any(|exp| exp.loc.is_closer(3.5, *p))
+			})
+			.map(|p| (p, score(p)))
+			.max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())
+			.map(|(p, _)| p)
+	}
+
+	/// Orders active creep tumors and given queens to spread creep.
+	///
+	/// Queens place tumors only if they have enough energy
+	/// (considering [`queen_energy_reserve`](CreepPlanner::queen_energy_reserve))
+	/// and aren't placing tumors already.
+	pub fn spread_creep(&mut self, queens: &Units) {
+		let mut planned = Vec::new();
+		let mut used = Vec::new();
+
+		for tag in self.creep_planner.tumors(TumorState::Active).collect::<Vec<_>>() {
+			let tumor = match self.units.my.structures.get(tag) {
+				Some(tumor) => tumor,
+				None => continue,
+			};
+			if let Some(pos) = self.tumor_position(tumor.position(), self.creep_planner.tumor_range, &planned)
+			{
+				tumor.command(AbilityId::BuildCreepTumorTumor, Target::Pos(pos), false);
+				planned.push(pos);
+				used.push(tag);
+			}
+		}
+
+		let reserve = self.creep_planner.queen_energy_reserve;
+		for queen in queens {
+			if queen.is_using(AbilityId::BuildCreepTumorQueen)
+				|| !queen.can_cast(AbilityId::BuildCreepTumorQueen)
+				|| !matches!(queen.energy(), Some(energy) if energy >= 25 + reserve)
+			{
+				continue;
+			}
+			if let Some(pos) = self.tumor_position(queen.position(), self.creep_planner.queen_range, &planned)
+			{
+				queen.command(AbilityId::BuildCreepTumorQueen, Target::Pos(pos), false);
+				planned.push(pos);
+			}
+		}
+
+		for tag in used {
+			self.creep_planner.mark_used(tag);
+		}
+	}
+}
@@ -0,0 +1,259 @@
+//! Danger zones created by effects and explosive units.
+//!
+//! Bot interprets raw [`effects`](crate::game_state::RawData::effects)
+//! and units like KD8 charges or phased disruptors as typed danger zones,
+//! stored in [`danger_zones`](crate::bot::Bot::danger_zones) field.
+//! ```
+//! use rust_sc2::prelude::*;
+//!
+//! #[bot]
+//! #[derive(Default)]
+//! struct MyBot;
+//! impl MyBot {
+//!     fn dodge(&mut self) {
+//!         for u in &self.units.my.units {
+//!             if let Some(pos) = self.dodge_position(u, 10) {
+//!                 u.move_to(Target::Pos(pos), false);
+//!             }
+//!         }
+//!     }
+//! }
+//! ```
+
+use crate::{
+	bot::Bot,
+	distance::*,
+	game_data::TargetType,
+	game_state::{Alliance, Effect},
+	geometry::Point2,
+	ids::{EffectId, UnitTypeId},
+	unit::Unit,
+	units::Units,
+};
+use rustc_hash::FxHashMap;
+use std::f32::consts::TAU;
+
+/// Static information about danger.
+#[derive(Debug, Copy, Clone)]
+pub struct DangerInfo {
+	/// Damage dealt to units in the zone.
+	pub damage: f32,
+	/// Game loops passed from spawn to the moment damage is dealt.
+	pub delay: u32,
+	/// Game loops zone exists after spawn, `None` if it's unknown (e.g. sieged liberator).
+	pub duration: Option<u32>,
+	/// Units affected by zone.
+	pub target: TargetType,
+	/// `true` if zone damages units of its owner too.
+	pub friendly_fire: bool,
+	/// Radius of zone, used for units, since effects have their own radius.
+	pub radius: f32,
+}
+impl DangerInfo {
+	const fn new(damage: f32, delay: u32, duration: Option<u32>, target: TargetType) -> Self {
+		Self {
+			damage,
+			delay,
+			duration,
+			target,
+			friendly_fire: false,
+			radius: 0.0,
+		}
+	}
+	const fn friendly_fire(mut self) -> Self {
+		self.friendly_fire = true;
+		self
+	}
+	const fn radius(mut self, radius: f32) -> Self {
+		self.radius = radius;
+		self
+	}
+
+	/// Returns danger information of given effect, `None` if effect is harmless.
+	pub fn of_effect(id: EffectId) -> Option<Self> {
+		use TargetType::*;
+
+		Some(match id {
+			EffectId::PsiStormPersistent => Self::new(80.0, 0, Some(64), Any).friendly_fire(),
+			EffectId::RavagerCorrosiveBileCP => Self::new(60.0, 56, Some(56), Any).friendly_fire(),
+			EffectId::NukePersistent => Self::new(300.0, 314, Some(314), Any).friendly_fire(),
+			EffectId::LurkerMP => Self::new(20.0, 0, Some(22), Ground),
+			EffectId::ThermalLancesForward => Self::new(20.0, 0, Some(8), Ground),
+			EffectId::LiberatorTargetMorphDelayPersistent => Self::new(75.0, 56, None, Ground),
+			EffectId::LiberatorTargetMorphPersistent => Self::new(75.0, 0, None, Ground),
+			EffectId::BlindingCloudCP => Self::new(0.0, 0, Some(128), Ground),
+			_ => return None,
+		})
+	}
+	/// Returns danger information of given unit type, `None` if unit isn't an explosive.
+	pub fn of_unit(id: UnitTypeId) -> Option<Self> {
+		Some(match id {
+			UnitTypeId::KD8Charge => Self::new(5.0, 32, Some(32), TargetType::Ground).radius(2.0),
+			UnitTypeId::DisruptorPhased => Self::new(145.0, 47, Some(47), TargetType::Ground)
+				.friendly_fire()
+				.radius(1.5),
+			_ => return None,
+		})
+	}
+}
+
+/// Source of the danger zone.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum DangerSource {
+	/// Zone is an effect.
+	Effect(EffectId),
+	/// Zone is an explosive unit with given type and tag.
+	Unit(UnitTypeId, u64),
+}
+
+/// Area where units will take damage.
+#[derive(Debug, Clone)]
+pub struct DangerZone {
+	/// What created the zone.
+	pub source: DangerSource,
+	/// Centers of covered circles.
+	pub positions: Vec<Point2>,
+	/// Radius of covered circles.
+	pub radius: f32,
+	/// Static information about the danger.
+	pub info: DangerInfo,
+	/// Game loop when zone was seen for the first time.
+	pub spawned: u32,
+	/// Game loops left until damage is dealt, `0` if it's already dealing damage.
+	pub frames_to_impact: u32,
+	/// Game loops left until zone disappears, `None` if it's unknown.
+	pub frames_left: Option<u32>,
+}
+impl DangerZone {
+	/// Checks if zone affects air or ground units.
+	pub fn affects(&self, flying: bool) -> bool {
+		match self.info.target {
+			TargetType::Any => true,
+			TargetType::Air => flying,
+			TargetType::Ground => !flying,
+		}
+	}
+	/// Checks if given circle intersects with the zone.
+	pub fn covers(&self, pos: Point2, radius: f32) -> bool {
+		self.positions
+			.iter()
+			.any(|p| p.is_closer(self.radius + radius, pos))
+	}
+	/// Checks if zone deals damage within given amount of game loops.
+	pub fn active_within(&self, frames: u32) -> bool {
+		self.frames_to_impact <= frames
+	}
+}
+
+/// All danger zones visible on the map.
+#[derive(Default, Clone)]
+pub struct DangerZones {
+	zones: Vec<DangerZone>,
+	spawns: FxHashMap<(DangerSource, (i32, i32)), u32>,
+}
+impl DangerZones {
+	/// All current danger zones.
+	pub fn zones(&self) -> &[DangerZone] {
+		&self.zones
+	}
+	/// Checks if given position is dangerous for air or ground units within given amount of game loops.
+	pub fn is_dangerous(&self, pos: Point2, flying: bool, frames: u32) -> bool {
+		self.zones
+			.iter()
+			.any(|z| z.affects(flying) && z.active_within(frames) && z.covers(pos, 0.0))
+	}
+	/// Returns zones, which will damage given unit within given amount of game loops.
+	pub fn threatening(&self, unit: &Unit, frames: u32) -> Vec<&DangerZone> {
+		let (pos, radius, flying) = (unit.position(), unit.radius(), unit.is_flying());
+		self.zones
+			.iter()
+			.filter(|z| z.affects(flying) && z.active_within(frames) && z.covers(pos, radius))
+			.collect()
+	}
+
+	pub(crate) fn update(&mut self, effects: &[Effect], units: &Units, game_loop: u32) {
+		let mut zones = Vec::new();
+		let mut spawns = FxHashMap::default();
+		let mut add_zone = |source: DangerSource, positions: Vec<Point2>, radius: f32, info: DangerInfo| {
+			let key = (
+				source,
+				positions
+					.first()
+					.map_or((0, 0), |p| ((p.x * 10.0) as i32, (p.y * 10.0) as i32)),
+			);
+			let spawned = self.spawns.get(&key).copied().unwrap_or(game_loop);
+			spawns.insert(key, spawned);
+
+			let passed = game_loop - spawned;
+			zones.push(DangerZone {
+				source,
+				positions,
+				radius,
+				info,
+				spawned,
+				frames_to_impact: info.delay.saturating_sub(passed),
+				frames_left: info.duration.map(|d| d.saturating_sub(passed)),
+			});
+		};
+
+		for e in effects {
+			if let Some(info) = DangerInfo::of_effect(e.id) {
+				if e.alliance != Alliance::Own || info.friendly_fire {
+					add_zone(DangerSource::Effect(e.id), e.positions.clone(), e.radius, info);
+				}
+			}
+		}
+		for u in units {
+			if let Some(info) = DangerInfo::of_unit(u.type_id()) {
+				if u.is_mine() && !info.friendly_fire {
+					continue;
+				}
+				add_zone(
+					DangerSource::Unit(u.type_id(), u.tag()),
+					vec![u.position()],
+					info.radius,
+					info,
+				);
+			}
+		}
+
+		self.zones = zones;
+		self.spawns = spawns;
+	}
+}
+
+impl Bot {
+	/// Returns the closest safe position, where given unit can escape from danger zones
+	/// damaging it within given amount of game loops.
+	/// `None` if unit isn't threatened or there's no safe position nearby.
+	pub fn dodge_position(&self, unit: &Unit, frames: u32) -> Option<Point2> {
+		let zones = self.danger_zones.threatening(unit, frames);
+		if zones.is_empty() {
+			return None;
+		}
+
+		let (pos, radius, flying) = (unit.position(), unit.radius(), unit.is_flying());
+		let max_distance = zones.iter().map(|z| z.radius * 2.0 + radius).fold(2.0, f32::max);
+		let is_safe = |p: Point2| {
+			(flying || self.is_pathable(p))
+				&& !self
+					.danger_zones
+					.zones
+					.iter()
+					.any(|z| z.affects(flying) && z.active_within(frames) && z.covers(p, radius))
+		};
+
+		const ANGLES: usize = 16;
+		let mut distance = 0.5;
+		while distance <= max_distance + 2.0 {
+			if let Some(p) = (0..ANGLES)
+				.map(|i| pos.towards_angle(i as f32 * TAU / ANGLES as f32, distance))
+				.find(|p| is_safe(*p))
+			{
+				return Some(p);
+			}
+			distance += 0.5;
+		}
+		None
+	}
+}
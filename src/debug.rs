@@ -1,36 +1,91 @@
 //! Items for interacting with Debug API.
 
 use crate::{
+	bot::Bot,
+	distance::*,
+	game_state::Alliance,
 	geometry::{Point2, Point3},
 	ids::UnitTypeId,
+	pixel_map::Visibility,
+	unit::Unit,
 	IntoProto,
 };
 use num_traits::ToPrimitive;
 use rustc_hash::FxHashSet;
 use sc2_proto::debug::{
 	DebugBox, DebugCommand as ProtoDebugCommand, DebugDraw as ProtoDebugDraw, DebugEndGame_EndResult,
-	DebugGameState as ProtoDebugGameState, DebugLine, DebugSetUnitValue_UnitValue, DebugSphere, DebugText,
+	DebugGameState as ProtoDebugGameState, DebugLine, DebugSetUnitValue_UnitValue, DebugSphere,
+	DebugTestProcess_Test, DebugText,
 };
+use std::{f32::consts::PI, fmt, mem, str::FromStr};
 
 type Color = (u32, u32, u32);
 type ScreenPos = (f32, f32);
 
+/// How long drawings made with [`draw_persistent`](Debugger::draw_persistent) are shown.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DrawLifetime {
+	/// Only on current step, same as usual drawings.
+	Step,
+	/// Given number of game loops.
+	Loops(u32),
+	/// Until cleared with [`clear_drawing`](Debugger::clear_drawing)
+	/// or [`clear_persistent`](Debugger::clear_persistent).
+	Forever,
+}
+
+#[derive(Debug, Clone)]
+struct PersistentDrawing {
+	key: Option<String>,
+	drawings: Vec<DebugDraw>,
+	lifetime: DrawLifetime,
+	started: Option<u32>,
+}
+
 /// Helper struct for interacting with Debug API.
 /// Can be accessed through [`debug`] field of bot.
 ///
 /// [`debug`]: crate::bot::Bot::debug
-#[derive(Default)]
 pub struct Debugger {
+	/// Grid overlays are drawn only for tiles within this distance from camera. [Default: `16`]
+	pub overlay_radius: f32,
 	debug_commands: Vec<DebugCommand>,
 	debug_drawings: Vec<DebugDraw>,
 	kill_tags: FxHashSet<u64>,
+	overlays: FxHashSet<Overlay>,
+	persistent: Vec<PersistentDrawing>,
+	persistent_drawn: bool,
+}
+impl Default for Debugger {
+	fn default() -> Self {
+		Self {
+			overlay_radius: 16.0,
+			debug_commands: Vec::new(),
+			debug_drawings: Vec::new(),
+			kill_tags: Default::default(),
+			overlays: Default::default(),
+			persistent: Vec::new(),
+			persistent_drawn: false,
+		}
+	}
 }
 impl Debugger {
-	pub(crate) fn get_commands(&mut self) -> &[DebugCommand] {
+	pub(crate) fn get_commands(&mut self, game_loop: u32) -> &[DebugCommand] {
 		let commands = &mut self.debug_commands;
 
+		self.persistent.retain_mut(|p| match p.lifetime {
+			DrawLifetime::Loops(loops) => game_loop < *p.started.get_or_insert(game_loop) + loops,
+			_ => true,
+		});
+		let persistent_drawn = mem::replace(&mut self.persistent_drawn, !self.persistent.is_empty());
+		self.debug_drawings
+			.extend(self.persistent.iter().flat_map(|p| p.drawings.iter().cloned()));
+
 		if !self.debug_drawings.is_empty() {
 			commands.push(DebugCommand::Draw(self.debug_drawings.drain(..).collect()));
+		} else if persistent_drawn {
+			// Game shows last drawings until new ones are sent, so expired ones are erased explicitly
+			commands.push(DebugCommand::Draw(Vec::new()));
 		}
 		if !self.kill_tags.is_empty() {
 			commands.push(DebugCommand::KillUnit(self.kill_tags.drain().collect()));
@@ -78,6 +133,167 @@ impl Debugger {
 	pub fn draw_sphere(&mut self, pos: Point3, radius: f32, color: Option<Color>) {
 		self.debug_drawings.push(DebugDraw::Sphere(pos, radius, color));
 	}
+	/// Draws circle on the ground plane (at height of `center`) with given radius.
+	pub fn draw_circle(&mut self, center: Point3, radius: f32, color: Option<Color>) {
+		let segments = ((radius * 4.0) as usize).clamp(12, 64);
+		let points = (0..segments)
+			.map(|i| {
+				let angle = 2.0 * PI * i as f32 / segments as f32;
+				Point3::new(
+					center.x + radius * angle.cos(),
+					center.y + radius * angle.sin(),
+					center.z,
+				)
+			})
+			.collect::<Vec<_>>();
+		self.draw_polyline(&points, true, color);
+	}
+	/// Draws arrow in game world from `p0` to `p1`, head of arrow is at `p1`.
+	pub fn draw_arrow(&mut self, p0: Point3, p1: Point3, color: Option<Color>) {
+		self.draw_line(p0, p1, color);
+
+		let (dx, dy) = (p0.x - p1.x, p0.y - p1.y);
+		let length = (dx * dx + dy * dy).sqrt();
+		if length < f32::EPSILON {
+			return;
+		}
+		let head = length.min(1.0) / length / 2.0;
+		let (dx, dy) = (dx * head, dy * head);
+		for (x, y) in [(dx - dy, dy + dx), (dx + dy, dy - dx)] {
+			self.draw_line(p1, Point3::new(p1.x + x, p1.y + y, p1.z), color);
+		}
+	}
+	/// Draws lines through given points, connecting last point with first one if `closed` is `true`.
+	pub fn draw_polyline(&mut self, points: &[Point3], closed: bool, color: Option<Color>) {
+		for pair in points.windows(2) {
+			self.draw_line(pair[0], pair[1], color);
+		}
+		if closed && points.len() > 2 {
+			self.draw_line(points[points.len() - 1], points[0], color);
+		}
+	}
+	/// Draws filled grid tile, which contains given position.
+	///
+	/// Debug API can't draw filled shapes, so fill is approximated with lines across the tile.
+	pub fn draw_tile(&mut self, pos: Point3, color: Option<Color>) {
+		const FILL_LINES: usize = 6;
+
+		let (x, y) = (pos.x.floor() + 0.05, pos.y.floor() + 0.05);
+		let z = pos.z + 0.05;
+		self.draw_box(Point3::new(x, y, pos.z), Point3::new(x + 0.9, y + 0.9, z), color);
+		for i in 1..FILL_LINES {
+			let offset = 0.9 * i as f32 / FILL_LINES as f32;
+			self.draw_line(
+				Point3::new(x, y + offset, z),
+				Point3::new(x + 0.9, y + offset, z),
+				color,
+			);
+		}
+	}
+	/// Draws label above unit with its type, health, shields, orders and given custom text.
+	pub fn draw_unit_info(&mut self, unit: &Unit, text: Option<&str>, color: Option<Color>) {
+		let mut label = format!("{:?} [{}]", unit.type_id(), unit.tag());
+		if let (Some(health), Some(health_max)) = (unit.health(), unit.health_max()) {
+			label.push_str(&format!("\nHP: {}/{}", health, health_max));
+		}
+		if let (Some(shield), Some(shield_max)) = (unit.shield(), unit.shield_max()) {
+			if shield_max > 0 {
+				label.push_str(&format!(" SP: {}/{}", shield, shield_max));
+			}
+		}
+		for order in unit.orders() {
+			label.push_str(&format!("\n{:?}", order.ability));
+		}
+		if let Some(text) = text {
+			label.push('\n');
+			label.push_str(text);
+		}
+		let pos = unit.position3d();
+		self.draw_text_world(
+			&label,
+			Point3::new(pos.x, pos.y, pos.z + unit.radius()),
+			color,
+			None,
+		);
+	}
+
+	/// Makes drawings done in `draw` closure persistent for given lifetime.
+	///
+	/// Drawings with given `key` replace previous drawings with the same key.
+	/// ```
+	/// # use rust_sc2::{debug::DrawLifetime, prelude::*};
+	/// # #[bot]
+	/// # #[derive(Default)]
+	/// # struct MyBot;
+	/// # impl MyBot {
+	/// #     fn on_step(&mut self) {
+	/// let target = self.enemy_start.to3(self.get_z_height(self.enemy_start));
+	/// self.debug.draw_persistent(Some("target"), DrawLifetime::Loops(224), |debug| {
+	///     debug.draw_circle(target, 3.0, Some((255, 0, 0)));
+	///     debug.draw_text_world("Attack", target, None, None);
+	/// });
+	/// #     }
+	/// # }
+	/// ```
+	pub fn draw_persistent<F>(&mut self, key: Option<&str>, lifetime: DrawLifetime, draw: F)
+	where
+		F: FnOnce(&mut Self),
+	{
+		let current = mem::take(&mut self.debug_drawings);
+		draw(self);
+		let drawings = mem::replace(&mut self.debug_drawings, current);
+
+		if lifetime == DrawLifetime::Step {
+			self.debug_drawings.extend(drawings);
+			return;
+		}
+		if let Some(key) = key {
+			self.clear_drawing(key);
+		}
+		self.persistent.push(PersistentDrawing {
+			key: key.map(|key| key.to_string()),
+			drawings,
+			lifetime,
+			started: None,
+		});
+	}
+	/// Removes persistent drawings with given key.
+	pub fn clear_drawing(&mut self, key: &str) {
+		self.persistent.retain(|p| p.key.as_deref() != Some(key));
+	}
+	/// Removes all persistent drawings.
+	pub fn clear_persistent(&mut self) {
+		self.persistent.clear();
+	}
+
+	/// Enables drawing of given overlay on every step.
+	pub fn enable_overlay(&mut self, overlay: Overlay) {
+		self.overlays.insert(overlay);
+	}
+	/// Disables drawing of given overlay.
+	pub fn disable_overlay(&mut self, overlay: Overlay) {
+		self.overlays.remove(&overlay);
+	}
+	/// Checks if given overlay is enabled.
+	pub fn is_overlay_enabled(&self, overlay: Overlay) -> bool {
+		self.overlays.contains(&overlay)
+	}
+	/// Returns all enabled overlays.
+	pub fn overlays(&self) -> &FxHashSet<Overlay> {
+		&self.overlays
+	}
+	/// Toggles overlay by its [name](Overlay::name), returns `true` if overlay is enabled now.
+	///
+	/// Returns `None` if overlay with given name doesn't exist.
+	pub fn toggle_overlay(&mut self, name: &str) -> Option<bool> {
+		let overlay = name.parse::<Overlay>().ok()?;
+		if self.overlays.remove(&overlay) {
+			Some(false)
+		} else {
+			self.overlays.insert(overlay);
+			Some(true)
+		}
+	}
 	/// Spawns units using given commands in format: (unit type, owner's player id, position, count).
 	pub fn create_units<'a, T>(&mut self, cmds: T)
 	where
@@ -104,6 +320,16 @@ impl Debugger {
 				.map(|(tag, unit_value, value)| DebugCommand::SetUnitValue(tag, unit_value, value)),
 		);
 	}
+	/// Makes game process hang, crash or exit after given delay in milliseconds.
+	/// Useful to test how bot handles failures of the game.
+	pub fn test_process(&mut self, test: ProcessTest, delay_ms: u32) {
+		self.debug_commands
+			.push(DebugCommand::TestProcess(test, delay_ms));
+	}
+	/// Sets score of bot (curriculum score).
+	pub fn set_score(&mut self, score: f32) {
+		self.debug_commands.push(DebugCommand::SetScore(score));
+	}
 	/// Ends game with Victory for bot
 	pub fn win_game(&mut self) {
 		self.debug_commands.push(DebugCommand::EndGame(true));
@@ -186,8 +412,8 @@ pub(crate) enum DebugCommand {
 	GameState(DebugGameState),
 	CreateUnit(UnitTypeId, Option<u32>, Point2, u32),
 	KillUnit(Vec<u64>),
-	// TestProcess,
-	// SetScore,
+	TestProcess(ProcessTest, u32),
+	SetScore(f32),
 	EndGame(bool),
 	SetUnitValue(u64, UnitValue, u32),
 }
@@ -207,6 +433,12 @@ impl IntoProto<ProtoDebugCommand> for &DebugCommand {
 				unit.set_quantity(*count);
 			}
 			DebugCommand::KillUnit(tags) => proto.mut_kill_unit().set_tag(tags.to_vec()),
+			DebugCommand::TestProcess(test, delay) => {
+				let cmd = proto.mut_test_process();
+				cmd.set_test(test.into_proto());
+				cmd.set_delay_ms(*delay as i32);
+			}
+			DebugCommand::SetScore(score) => proto.mut_score().set_score(*score),
 			DebugCommand::EndGame(win) => {
 				let end_game = proto.mut_end_game();
 				if *win {
@@ -326,6 +558,26 @@ impl IntoProto<DebugSetUnitValue_UnitValue> for UnitValue {
 	}
 }
 
+/// Failure of game process used in [`test_process`](Debugger::test_process) command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProcessTest {
+	/// Game stops responding.
+	Hang,
+	/// Game crashes.
+	Crash,
+	/// Game exits.
+	Exit,
+}
+impl IntoProto<DebugTestProcess_Test> for ProcessTest {
+	fn into_proto(self) -> DebugTestProcess_Test {
+		match self {
+			ProcessTest::Hang => DebugTestProcess_Test::hang,
+			ProcessTest::Crash => DebugTestProcess_Test::crash,
+			ProcessTest::Exit => DebugTestProcess_Test::exit,
+		}
+	}
+}
+
 #[derive(Debug, Clone, Copy)]
 pub(crate) enum DebugGameState {
 	ShowMap,
@@ -359,3 +611,223 @@ impl IntoProto<ProtoDebugGameState> for DebugGameState {
 		}
 	}
 }
+
+/// Ready-made overlays for visualization of map analysis,
+/// which can be toggled with [`Debugger::toggle_overlay`] by their names at runtime.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Overlay {
+	/// Pathable (green) and not pathable (red) tiles.
+	PathingGrid,
+	/// Placeable tiles.
+	PlacementGrid,
+	/// Terrain height of tiles, from dark (low) to bright (high).
+	TerrainHeight,
+	/// Tiles with creep.
+	Creep,
+	/// Visible (yellow), fogged (gray) and hidden (dark) tiles.
+	Visibility,
+	/// Expansions with lines to their resources, colored by alliance.
+	Expansions,
+	/// Upper and lower points of ramps and positions of buildings in walls on own ramp.
+	Ramps,
+	/// Vision blockers.
+	VisionBlockers,
+}
+impl Overlay {
+	/// All available overlays.
+	pub const ALL: [Overlay; 8] = [
+		Overlay::PathingGrid,
+		Overlay::PlacementGrid,
+		Overlay::TerrainHeight,
+		Overlay::Creep,
+		Overlay::Visibility,
+		Overlay::Expansions,
+		Overlay::Ramps,
+		Overlay::VisionBlockers,
+	];
+
+	/// Name of overlay, used to toggle it at runtime.
+	pub fn name(self) -> &'static str {
+		match self {
+			Overlay::PathingGrid => "pathing",
+			Overlay::PlacementGrid => "placement",
+			Overlay::TerrainHeight => "height",
+			Overlay::Creep => "creep",
+			Overlay::Visibility => "visibility",
+			Overlay::Expansions => "expansions",
+			Overlay::Ramps => "ramps",
+			Overlay::VisionBlockers => "vision_blockers",
+		}
+	}
+}
+impl fmt::Display for Overlay {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str(self.name())
+	}
+}
+impl FromStr for Overlay {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let name = s.trim().to_lowercase();
+		Overlay::ALL
+			.iter()
+			.find(|o| o.name() == name)
+			.copied()
+			.ok_or_else(|| format!("Unknown overlay: {}", s))
+	}
+}
+
+const OVERLAY_GREEN: Color = (0, 255, 0);
+const OVERLAY_RED: Color = (255, 0, 0);
+const OVERLAY_BLUE: Color = (0, 128, 255);
+const OVERLAY_PURPLE: Color = (160, 0, 255);
+const OVERLAY_YELLOW: Color = (255, 255, 0);
+const OVERLAY_GRAY: Color = (128, 128, 128);
+const OVERLAY_DARK: Color = (40, 40, 40);
+const OVERLAY_WHITE: Color = (255, 255, 255);
+
+impl Bot {
+	/// Tiles of playable area within overlay radius from camera.
+	fn overlay_tiles(&self) -> Vec<(usize, usize)> {
+		let area = self.game_info.playable_area;
+		let camera = self.state.observation.raw.camera;
+		let radius = self.debug.overlay_radius;
+
+		let x0 = ((camera.x - radius).max(area.x0 as f32) as usize).max(area.x0);
+		let y0 = ((camera.y - radius).max(area.y0 as f32) as usize).max(area.y0);
+		let x1 = ((camera.x + radius).max(0.0) as usize).min(area.x1);
+		let y1 = ((camera.y + radius).max(0.0) as usize).min(area.y1);
+
+		(x0..x1)
+			.flat_map(|x| (y0..y1).map(move |y| (x, y)))
+			.filter(|&(x, y)| camera.is_closer(radius, Point2::new(x as f32 + 0.5, y as f32 + 0.5)))
+			.collect()
+	}
+	fn overlay_tile(&self, drawings: &mut Vec<DebugDraw>, pos: (usize, usize), color: Color) {
+		let z = self.get_z_height(pos);
+		let (x, y) = (pos.0 as f32, pos.1 as f32);
+		drawings.push(DebugDraw::Box(
+			Point3::new(x + 0.25, y + 0.25, z),
+			Point3::new(x + 0.75, y + 0.75, z + 0.25),
+			Some(color),
+		));
+	}
+	fn overlay_point(&self, drawings: &mut Vec<DebugDraw>, pos: Point2, label: &str, color: Color) {
+		let pos3 = pos.to3(self.get_z_height(pos) + 0.5);
+		drawings.push(DebugDraw::Sphere(pos3, 0.5, Some(color)));
+		if !label.is_empty() {
+			drawings.push(DebugDraw::Text(
+				label.to_string(),
+				DebugPos::World(pos3),
+				Some(color),
+				None,
+			));
+		}
+	}
+
+	/// Draws all enabled overlays.
+	pub(crate) fn draw_overlays(&mut self) {
+		if self.debug.overlays.is_empty() {
+			return;
+		}
+		let mut drawings = Vec::new();
+		let overlays = &self.debug.overlays;
+
+		let grid = [
+			Overlay::PathingGrid,
+			Overlay::PlacementGrid,
+			Overlay::TerrainHeight,
+			Overlay::Creep,
+			Overlay::Visibility,
+		];
+		if grid.iter().any(|o| overlays.contains(o)) {
+			for pos in self.overlay_tiles() {
+				for overlay in grid.iter().filter(|o| overlays.contains(o)) {
+					let color = match overlay {
+						Overlay::PathingGrid if self.is_pathable(pos) => OVERLAY_GREEN,
+						Overlay::PathingGrid => OVERLAY_RED,
+						Overlay::PlacementGrid if self.is_placeable(pos) => OVERLAY_BLUE,
+						Overlay::TerrainHeight => {
+							let h = self.get_height(pos) as u32;
+							(h, h, h)
+						}
+						Overlay::Creep if self.has_creep(pos) => OVERLAY_PURPLE,
+						Overlay::Visibility => match self.state.observation.raw.visibility.get(pos) {
+							Some(Visibility::Visible) => OVERLAY_YELLOW,
+							Some(Visibility::Fogged) => OVERLAY_GRAY,
+							_ => OVERLAY_DARK,
+						},
+						_ => continue,
+					};
+					self.overlay_tile(&mut drawings, pos, color);
+				}
+			}
+		}
+
+		if overlays.contains(&Overlay::Expansions) {
+			for exp in &self.expansions {
+				let color = match exp.alliance {
+					Alliance::Own => OVERLAY_GREEN,
+					Alliance::Enemy => OVERLAY_RED,
+					_ => OVERLAY_WHITE,
+				};
+				self.overlay_point(&mut drawings, exp.loc, &format!("{:?}", exp.alliance), color);
+
+				let loc = exp.loc.to3(self.get_z_height(exp.loc) + 0.5);
+				let resources = exp.minerals.iter().chain(&exp.geysers);
+				for r in resources.filter_map(|tag| self.units.resources.get(*tag)) {
+					let pos = r.position();
+					drawings.push(DebugDraw::Line(
+						loc,
+						pos.to3(self.get_z_height(pos) + 0.5),
+						Some(color),
+					));
+				}
+			}
+		}
+
+		if overlays.contains(&Overlay::Ramps) {
+			for ramp in &self.ramps.all {
+				for pos in ramp.upper() {
+					self.overlay_tile(&mut drawings, pos, OVERLAY_GREEN);
+				}
+				for pos in ramp.lower() {
+					self.overlay_tile(&mut drawings, pos, OVERLAY_RED);
+				}
+			}
+
+			let ramp = &self.ramps.my;
+			let mut wall = Vec::new();
+			if let Some(depots) = ramp.corner_depots() {
+				wall.extend(depots.iter().map(|d| (*d, "Depot")));
+			}
+			if let Some(depot) = ramp.depot_in_middle() {
+				wall.push((depot, "Middle depot"));
+			}
+			if let Some(barracks) = ramp.barracks_correct_placement() {
+				wall.push((barracks, "Barracks"));
+			}
+			if let Some(pylon) = ramp.protoss_wall_pylon() {
+				wall.push((pylon, "Pylon"));
+			}
+			if let Some(buildings) = ramp.protoss_wall_buildings() {
+				wall.extend(buildings.iter().map(|b| (*b, "Gateway")));
+			}
+			if let Some(warpin) = ramp.protoss_wall_warpin() {
+				wall.push((warpin, "Warpin"));
+			}
+			for (pos, label) in wall {
+				self.overlay_point(&mut drawings, pos, label, OVERLAY_YELLOW);
+			}
+		}
+
+		if overlays.contains(&Overlay::VisionBlockers) {
+			for pos in &self.vision_blockers {
+				self.overlay_tile(&mut drawings, (*pos).into(), OVERLAY_BLUE);
+			}
+		}
+
+		self.debug.debug_drawings.extend(drawings);
+	}
+}
@@ -2,7 +2,8 @@
 #![allow(missing_docs)]
 
 use crate::{
-	ids::{AbilityId, BuffId, EffectId, UnitTypeId, UpgradeId},
+	consts::{ABILITY_COOLDOWNS, ABILITY_ENERGY_COSTS},
+	ids::{AbilityId, BuffId, EffectId, IdKind, UnitTypeId, UnknownId, UpgradeId},
 	player::Race,
 	FromProto, TryFromProto,
 };
@@ -34,9 +35,58 @@ pub struct GameData {
 	pub buffs: FxHashMap<BuffId, BuffData>,
 	/// Information about effects mapped to `EffectId`s.
 	pub effects: FxHashMap<EffectId, EffectData>,
+	/// Information about ids unknown to the library (e.g. from mods or newer game versions).
+	pub unknown: Vec<UnknownData>,
+}
+impl GameData {
+	/// Returns information about given id unknown to the library.
+	pub fn unknown(&self, kind: IdKind, id: u64) -> Option<&UnknownData> {
+		self.unknown
+			.iter()
+			.find(|data| data.id.kind == kind && data.id.id == id)
+	}
 }
 impl FromProto<ResponseData> for GameData {
 	fn from_proto(data: ResponseData) -> Self {
+		let mut unknown = Vec::new();
+		let mut add_unknown = |kind, id: u32, name: &str, costs: (u32, u32)| {
+			if !name.is_empty() {
+				unknown.push(UnknownData {
+					id: UnknownId { kind, id: id as u64 },
+					name: name.to_string(),
+					mineral_cost: costs.0,
+					vespene_cost: costs.1,
+				});
+			}
+		};
+		for a in data.get_abilities() {
+			if AbilityId::from_u32(a.get_ability_id()).is_none() {
+				add_unknown(IdKind::Ability, a.get_ability_id(), a.get_link_name(), (0, 0));
+			}
+		}
+		for u in data.get_units() {
+			if UnitTypeId::from_u32(u.get_unit_id()).is_none() {
+				let costs = (u.get_mineral_cost(), u.get_vespene_cost());
+				add_unknown(IdKind::Unit, u.get_unit_id(), u.get_name(), costs);
+			}
+		}
+		for u in data.get_upgrades() {
+			if UpgradeId::from_u32(u.get_upgrade_id()).is_none() {
+				let costs = (u.get_mineral_cost(), u.get_vespene_cost());
+				add_unknown(IdKind::Upgrade, u.get_upgrade_id(), u.get_name(), costs);
+			}
+		}
+		for b in data.get_buffs() {
+			if BuffId::from_u32(b.get_buff_id()).is_none() {
+				add_unknown(IdKind::Buff, b.get_buff_id(), b.get_name(), (0, 0));
+			}
+		}
+		for e in data.get_effects() {
+			if EffectId::from_u32(e.get_effect_id()).is_none() {
+				add_unknown(IdKind::Effect, e.get_effect_id(), e.get_name(), (0, 0));
+			}
+		}
+
 		Self {
 			abilities: data
 				.get_abilities()
@@ -63,10 +113,23 @@ impl FromProto<ResponseData> for GameData {
 				.iter()
 				.filter_map(|e| EffectData::try_from_proto(e).map(|data| (data.id, data)))
 				.collect(),
+			unknown,
 		}
 	}
 }
 
+/// Name and cost of id unknown to the library, provided by the game.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct UnknownData {
+	pub id: UnknownId,
+	pub name: String,
+	/// Mineral cost of unit or upgrade.
+	pub mineral_cost: u32,
+	/// Vespene cost of unit or upgrade.
+	pub vespene_cost: u32,
+}
+
 /// Cost of an item (`UnitTypeId` or `UpgradeId`) in resources, supply and time.
 #[derive(Debug, Default)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -213,11 +276,18 @@ pub struct AbilityData {
 	pub is_instant_placement: bool,
 	/// Maximum range to target of the ability.
 	pub cast_range: Option<f32>,
+	/// Cooldown of the ability in game loops.
+	/// Not provided by the API, taken from [`ABILITY_COOLDOWNS`](crate::consts::ABILITY_COOLDOWNS).
+	pub cooldown: Option<u32>,
+	/// Energy needed to use the ability.
+	/// Not provided by the API, taken from [`ABILITY_ENERGY_COSTS`](crate::consts::ABILITY_ENERGY_COSTS).
+	pub energy_cost: Option<u32>,
 }
 impl TryFromProto<&ProtoAbilityData> for AbilityData {
 	fn try_from_proto(a: &ProtoAbilityData) -> Option<Self> {
+		let id = AbilityId::from_u32(a.get_ability_id())?;
 		Some(Self {
-			id: AbilityId::from_u32(a.get_ability_id())?,
+			id,
 			link_name: a.get_link_name().to_string(),
 			link_index: a.get_link_index(),
 			button_name: a.button_name.as_ref().cloned(),
@@ -232,6 +302,8 @@ impl TryFromProto<&ProtoAbilityData> for AbilityData {
 			footprint_radius: a.footprint_radius,
 			is_instant_placement: a.get_is_instant_placement(),
 			cast_range: a.cast_range,
+			cooldown: ABILITY_COOLDOWNS.get(&id).copied(),
+			energy_cost: ABILITY_ENERGY_COSTS.get(&id).copied(),
 		})
 	}
 }
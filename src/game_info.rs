@@ -9,10 +9,13 @@ use crate::{
 };
 use rustc_hash::FxHashMap;
 use sc2_proto::sc2api::ResponseGameInfo;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use std::path::Path;
 
 /// Structure where all map information stored.
 #[derive(Default, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct GameInfo {
 	/// Map name bot playing on, which depends on sc2 localization language.
 	pub map_name: String,
@@ -40,6 +43,22 @@ pub struct GameInfo {
 	/// Center of the map.
 	pub map_center: Point2,
 }
+impl GameInfo {
+	/// Flat map of given size, where all tiles are pathable and placeable.
+	///
+	/// Useful to construct [`Snapshot`](crate::snapshot::Snapshot) without running the game.
+	pub fn flat(width: usize, height: usize) -> Self {
+		Self {
+			map_size: Size::new(width, height),
+			pathing_grid: PixelMap::default((width, height)),
+			terrain_height: Rs::new(ByteMap::default((width, height))),
+			placement_grid: PixelMap::default((width, height)),
+			playable_area: Rect::new(0, 0, width, height),
+			map_center: Point2::new(width as f32 / 2.0, height as f32 / 2.0),
+			..Default::default()
+		}
+	}
+}
 impl FromProto<ResponseGameInfo> for GameInfo {
 	fn from_proto(game_info: ResponseGameInfo) -> Self {
 		let start_raw = game_info.get_start_raw();
@@ -106,6 +125,7 @@ impl FromProto<ResponseGameInfo> for GameInfo {
 
 /// Information about player.
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct PlayerInfo {
 	/// Player id.
 	pub id: u32,
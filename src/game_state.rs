@@ -11,19 +11,21 @@ use crate::{
 	units::Units,
 	Event, FromProto, Player, SC2Result,
 };
-use num_traits::FromPrimitive;
 use rustc_hash::FxHashSet;
 use sc2_proto::{
 	query::RequestQueryAvailableAbilities,
 	raw::{Alliance as ProtoAlliance, PowerSource as ProtoPowerSource},
 	sc2api::{Alert as ProtoAlert, Request, ResponseObservation},
 };
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use std::ops::{Deref, DerefMut};
 
 /// Information about current state on current step.
 ///
 /// Can be accessed through [`state`](crate::bot::Bot::state) field.
 #[derive(Default, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct GameState {
 	/// Actions executed on previous step.
 	pub actions: Vec<Action>,
@@ -79,12 +81,11 @@ where
 	obs.abilities = res_obs
 		.get_abilities()
 		.iter()
-		.map(|a| AvailableAbility {
-			id: {
-				let id = a.get_ability_id();
-				AbilityId::from_i32(id).unwrap_or_else(|| panic!("There's no `AbilityId` with value {}", id))
-			},
-			requires_point: a.get_requires_point(),
+		.filter_map(|a| {
+			Some(AvailableAbility {
+				id: AbilityId::parse_id(a.get_ability_id())?,
+				requires_point: a.get_requires_point(),
+			})
 		})
 		.collect();
 	obs.score = Score::from_proto(res_obs.get_score());
@@ -119,15 +120,14 @@ where
 	raw.effects = res_raw
 		.get_effects()
 		.iter()
-		.map(|e| Effect {
-			id: {
-				let id = e.get_effect_id();
-				EffectId::from_u32(id).unwrap_or_else(|| panic!("There's no `EffectId` with value {}", id))
-			},
-			positions: e.get_pos().iter().map(Point2::from_proto).collect(),
-			alliance: Alliance::from_proto(e.get_alliance()),
-			owner: e.get_owner() as u32,
-			radius: e.get_radius(),
+		.filter_map(|e| {
+			Some(Effect {
+				id: EffectId::parse_id(e.get_effect_id())?,
+				positions: e.get_pos().iter().map(Point2::from_proto).collect(),
+				alliance: Alliance::from_proto(e.get_alliance()),
+				owner: e.get_owner() as u32,
+				radius: e.get_radius(),
+			})
 		})
 		.collect();
 	raw.radars = res_raw
@@ -149,6 +149,7 @@ where
 	for u in &dead_units {
 		let alliance = if bot.owned_tags.remove(u) {
 			bot.available_frames.write_lock().remove(u);
+			bot.ability_cooldowns.write_lock().remove(u);
 			bot.under_construction.remove(u);
 			Some(Alliance::Own)
 		} else {
@@ -177,6 +178,10 @@ where
 			}
 		};
 
+		if alliance == Some(Alliance::Own) {
+			bot.squads.unassign(*u);
+			bot.roles.release(*u);
+		}
 		events.push(Event::UnitDestroyed(*u, alliance));
 	}
 
@@ -187,7 +192,7 @@ where
 	*raw.upgrades.write_lock() = raw_player
 		.get_upgrade_ids()
 		.iter()
-		.map(|u| UpgradeId::from_u32(*u).unwrap_or_else(|| panic!("There's no `UpgradeId` with value {}", u)))
+		.filter_map(|u| UpgradeId::parse_id(*u))
 		.collect::<FxHashSet<_>>();
 
 	// Map
@@ -216,12 +221,26 @@ where
 				a.get_unit_tag(),
 				a.get_abilities()
 					.iter()
-					.filter_map(|ab| AbilityId::from_i32(ab.get_ability_id()))
+					.filter_map(|ab| AbilityId::parse_id(ab.get_ability_id()))
 					.collect(),
 			)
 		})
 		.collect();
 
+	// Validating locally tracked cooldowns
+	let game_loop = bot.state.observation.game_loop();
+	let abilities_units = bot.abilities_units.read_lock();
+	bot.ability_cooldowns.write_lock().retain(|tag, cooldowns| {
+		let available = abilities_units.get(tag);
+		cooldowns.retain(|ability, cooldown| {
+			// Ability is still available after command was sent -> it wasn't used yet
+			cooldown.ready > game_loop
+				&& !(cooldown.ordered < game_loop && matches!(available, Some(a) if a.contains(ability)))
+		});
+		!cooldowns.is_empty()
+	});
+	drop(abilities_units);
+
 	// Get visiblity
 	let visibility = VisibilityMap::from_proto(map_state.get_visibility());
 	// Get units
@@ -241,6 +260,8 @@ where
 	let mut owned_tags = vec![];
 	let mut under_construction = vec![];
 	let mut construction_complete = vec![];
+	let mut created = vec![];
+	let mut hatched = vec![];
 	for (tag, u) in bot.units.my.all.pairs() {
 		if !bot.owned_tags.contains(tag) {
 			owned_tags.push(*tag);
@@ -254,11 +275,14 @@ where
 					}
 				}
 			} else {
+				created.push(u.clone());
 				events.push(Event::UnitCreated(*tag));
 			}
 		} else if bot.under_construction.contains(tag) && u.is_ready() {
 			construction_complete.push(*tag);
 			events.push(Event::ConstructionComplete(*tag));
+		} else if bot.squads.is_hatched(u) {
+			hatched.push(u.clone());
 		}
 	}
 	for tag in owned_tags {
@@ -270,6 +294,9 @@ where
 	for tag in construction_complete {
 		bot.under_construction.remove(&tag);
 	}
+	for u in created.into_iter().chain(hatched) {
+		bot.squads.rally(&u);
+	}
 
 	if bot.enemy_race.is_random() {
 		if let Some(race) = bot
@@ -290,6 +317,7 @@ where
 
 /// Messege in game chat.
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ChatMessage {
 	/// Id of player who sent that message.
 	pub player_id: u32,
@@ -300,6 +328,7 @@ pub struct ChatMessage {
 /// Bot's observation stored here.
 /// Can be accessed through [`state.observation`](GameState::observation).
 #[derive(Default, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Observation {
 	pub(crate) game_loop: Rs<LockU32>,
 	/// Common information from the observation.
@@ -322,6 +351,7 @@ impl Observation {
 /// Bot's observation stored here.
 /// Can be accessed through [`state.observation.raw`](Observation::raw).
 #[derive(Default, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct RawData {
 	/// Protoss power from pylons.
 	pub psionic_matrix: Vec<PsionicMatrix>,
@@ -343,6 +373,7 @@ pub struct RawData {
 
 /// Power matrix from the pylon or warp prism, used to give power to buildings and warp units on it.
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct PsionicMatrix {
 	/// Position of psionic matrix source.
 	pub pos: Point2,
@@ -366,6 +397,7 @@ impl FromProto<&ProtoPowerSource> for PsionicMatrix {
 ///
 /// All effects stored in [state.observation.raw.effects](RawData::effects).
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Effect {
 	/// Type of the effect.
 	pub id: EffectId,
@@ -381,6 +413,7 @@ pub struct Effect {
 
 /// The alliance of unit or effect to your bot.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Alliance {
 	/// Your own objects.
 	Own,
@@ -418,6 +451,7 @@ impl FromProto<ProtoAlliance> for Alliance {
 
 /// Radar point on the map.
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Radar {
 	/// Position where radar is.
 	pub pos: Point2,
@@ -427,6 +461,7 @@ pub struct Radar {
 
 /// Common information of player.
 #[derive(Default, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Common {
 	/// In-game player id.
 	pub player_id: u32,
@@ -457,6 +492,7 @@ pub struct Common {
 #[allow(missing_docs)]
 #[allow(clippy::enum_variant_names)]
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Alert {
 	AlertError,
 	AddOnComplete,
@@ -511,6 +547,7 @@ impl FromProto<ProtoAlert> for Alert {
 }
 
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct AvailableAbility {
 	pub id: AbilityId,
 	pub requires_point: bool,
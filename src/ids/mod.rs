@@ -1,4 +1,4 @@
-//! Auto generated with `generate_ids.py` script from `stableid.json`
+//! Auto generated with `cargo xtask ids` from `stableid.json`
 //! ids of units, ablities, upgrades, buffs and effects.
 #![allow(missing_docs)]
 
@@ -15,3 +15,7 @@ pub use buff_id::BuffId;
 pub use effect_id::EffectId;
 
 mod impls;
+mod unknown;
+
+pub use unknown::{unknown_ids, IdKind, UnknownId};
+pub(crate) use unknown::ParseId;
@@ -0,0 +1,74 @@
+//! Handling of ids unknown to the library (e.g. from mods or newer game versions).
+
+use super::{AbilityId, BuffId, EffectId, UnitTypeId, UpgradeId};
+use num_traits::FromPrimitive;
+use rustc_hash::FxHashSet;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+
+/// Kind of id.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum IdKind {
+	Unit,
+	Ability,
+	Upgrade,
+	Buff,
+	Effect,
+}
+
+/// Raw id, which has no variant in corresponding enum.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct UnknownId {
+	pub kind: IdKind,
+	pub id: u64,
+}
+
+lazy_static! {
+	static ref UNKNOWN_IDS: Mutex<FxHashSet<UnknownId>> = Default::default();
+}
+
+/// Returns all unknown ids received from the game in observations and skipped.
+pub fn unknown_ids() -> Vec<UnknownId> {
+	let ids = UNKNOWN_IDS.lock().unwrap_or_else(|e| e.into_inner());
+	ids.iter().copied().collect()
+}
+
+fn report(kind: IdKind, id: u64) {
+	let mut ids = UNKNOWN_IDS.lock().unwrap_or_else(|e| e.into_inner());
+	if ids.insert(UnknownId { kind, id }) {
+		warn!("Unknown {:?} id {} received from the game, skipping it", kind, id);
+	}
+}
+
+/// Conversion of raw ids, which doesn't panic on unknown ones.
+pub(crate) trait ParseId: FromPrimitive {
+	const KIND: IdKind;
+
+	/// Converts raw id, unknown ids are logged once and stored in [`unknown_ids`].
+	fn parse_id<T: TryInto<u64>>(id: T) -> Option<Self> {
+		let id = id.try_into().ok()?;
+		let parsed = Self::from_u64(id);
+		if parsed.is_none() {
+			report(Self::KIND, id);
+		}
+		parsed
+	}
+}
+impl ParseId for UnitTypeId {
+	const KIND: IdKind = IdKind::Unit;
+}
+impl ParseId for AbilityId {
+	const KIND: IdKind = IdKind::Ability;
+}
+impl ParseId for UpgradeId {
+	const KIND: IdKind = IdKind::Upgrade;
+}
+impl ParseId for BuffId {
+	const KIND: IdKind = IdKind::Buff;
+}
+impl ParseId for EffectId {
+	const KIND: IdKind = IdKind::Effect;
+}
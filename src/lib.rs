@@ -366,10 +366,16 @@ pub mod prelude {
 mod paths;
 
 pub mod action;
+pub mod addons;
+pub mod aoe;
 pub mod api;
 pub mod bot;
+pub mod chrono;
 pub mod client;
+pub mod console;
 pub mod consts;
+pub mod creep;
+pub mod danger;
 pub mod debug;
 pub mod distance;
 pub mod game_data;
@@ -377,13 +383,25 @@ pub mod game_info;
 pub mod game_state;
 pub mod geometry;
 pub mod ids;
+pub mod micro;
+pub mod orbital;
 pub mod pixel_map;
 pub mod player;
 pub mod ramp;
+pub mod roles;
+pub mod scenario;
 pub mod score;
+pub mod scouting;
+pub mod snapshot;
+pub mod squads;
+pub mod static_data;
+pub mod strategy;
+pub mod transport;
 pub mod unit;
 pub mod units;
 pub mod utils;
+pub mod warp;
+pub mod zerg;
 
 use game_state::Alliance;
 use player::{GameResult, Race};
@@ -0,0 +1,200 @@
+//! Helpers for kiting and target fire.
+//!
+//! [`Micro`] decides what unit should do against given enemies:
+//! shoot the best target in range, step back while weapon is on cooldown or advance.
+//! ```
+//! use rust_sc2::{micro::Micro, prelude::*};
+//!
+//! #[bot]
+//! #[derive(Default)]
+//! struct MyBot;
+//! impl MyBot {
+//!     fn fight(&mut self) {
+//!         let micro = Micro::default();
+//!         for u in &self.units.my.units {
+//!             micro.command(u, &self.units.enemy.all).execute(u);
+//!         }
+//!     }
+//! }
+//! ```
+
+use crate::{
+	action::Target, distance::*, game_data::Attribute, geometry::Point2, ids::UnitTypeId, unit::Unit,
+	units::Units,
+};
+use rustc_hash::FxHashMap;
+
+/// Command returned by [`Micro::command`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum MicroCommand {
+	/// Shoot target with given tag.
+	Attack(u64),
+	/// Step back to given position while weapon is on cooldown.
+	Retreat(Point2),
+	/// Move closer to enemies (attack-move to given position).
+	Advance(Point2),
+	/// Nothing to do now (e.g. waiting for weapon cooldown while being safe).
+	Wait,
+}
+impl MicroCommand {
+	/// Gives corresponding order to the unit.
+	pub fn execute(self, unit: &Unit) {
+		match self {
+			MicroCommand::Attack(tag) => unit.attack(Target::Tag(tag), false),
+			MicroCommand::Retreat(pos) => unit.move_to(Target::Pos(pos), false),
+			MicroCommand::Advance(pos) => unit.attack(Target::Pos(pos), false),
+			MicroCommand::Wait => {}
+		}
+	}
+}
+
+/// Weights used to score targets, the higher score the more desirable target is.
+#[derive(Debug, Clone)]
+pub struct TargetPriority {
+	/// Weight of target's dps vs attacker divided by target's hits. [Default: `10`]
+	pub dps_per_hp: f32,
+	/// Weight of target's missing hits percentage. [Default: `1`]
+	pub low_health: f32,
+	/// Added when attacker has damage bonus vs target's attribute. [Default: `1`]
+	pub bonus_vs_attribute: f32,
+	/// Additional scores for attributes.
+	pub attributes: Vec<(Attribute, f32)>,
+	/// Additional scores for unit types.
+	pub types: FxHashMap<UnitTypeId, f32>,
+}
+impl Default for TargetPriority {
+	fn default() -> Self {
+		Self {
+			dps_per_hp: 10.0,
+			low_health: 1.0,
+			bonus_vs_attribute: 1.0,
+			attributes: Vec::new(),
+			types: Default::default(),
+		}
+	}
+}
+impl TargetPriority {
+	/// Returns score of target for given attacker.
+	pub fn score(&self, attacker: &Unit, target: &Unit) -> f32 {
+		let mut score = 0.0;
+
+		if let Some(hits) = target.hits() {
+			let dps = target.real_weapon_vs(attacker).0;
+			score += self.dps_per_hp * dps / (hits.max(1) as f32);
+		}
+		if let Some(percentage) = target.hits_percentage() {
+			score += self.low_health * (1.0 - percentage);
+		}
+		if let Some((attribute, _)) = attacker.damage_bonus() {
+			if target.has_attribute(attribute) {
+				score += self.bonus_vs_attribute;
+			}
+		}
+		for (attribute, bonus) in &self.attributes {
+			if target.has_attribute(*attribute) {
+				score += bonus;
+			}
+		}
+		if let Some(bonus) = self.types.get(&target.type_id()) {
+			score += bonus;
+		}
+
+		score
+	}
+}
+
+/// Configurable stutter-step logic.
+#[derive(Debug, Clone)]
+pub struct Micro {
+	/// Scoring of targets.
+	pub priority: TargetPriority,
+	/// Additional distance kept from enemies' range when stepping back. [Default: `0.5`]
+	pub safety_gap: f32,
+	/// Enemies further than this distance are ignored. [Default: `15`]
+	pub search_range: f32,
+}
+impl Default for Micro {
+	fn default() -> Self {
+		Self {
+			priority: Default::default(),
+			safety_gap: 0.5,
+			search_range: 15.0,
+		}
+	}
+}
+impl Micro {
+	/// Returns best target in range of unit's weapon.
+	pub fn best_target_in_range<'a>(&self, unit: &Unit, enemies: &'a Units) -> Option<&'a Unit> {
+		enemies
+			.iter()
+			.filter(|e| unit.can_attack_unit(e) && unit.in_real_range(e, 0.0))
+			.max_by(|a, b| {
+				self.priority
+					.score(unit, a)
+					.partial_cmp(&self.priority.score(unit, b))
+					.unwrap()
+			})
+	}
+	/// Returns best target to approach, considering score and distance to it.
+	pub fn best_target<'a>(&self, unit: &Unit, enemies: &'a Units) -> Option<&'a Unit> {
+		let value = |e: &Unit| self.priority.score(unit, e) - unit.distance(e) / self.search_range;
+		enemies
+			.iter()
+			.filter(|e| unit.can_attack_unit(e) && unit.is_closer(self.search_range, *e))
+			.max_by(|a, b| value(a).partial_cmp(&value(b)).unwrap())
+	}
+
+	/// Decides what given unit should do against given enemies.
+	///
+	/// Melee units always attack, while ranged ones step back from threats
+	/// while their weapon is on cooldown.
+	pub fn command(&self, unit: &Unit, enemies: &Units) -> MicroCommand {
+		let ready = !unit.on_cooldown();
+		let in_range = self.best_target_in_range(unit, enemies);
+
+		if let Some(target) = in_range {
+			if ready || unit.is_melee() {
+				return MicroCommand::Attack(target.tag());
+			}
+		}
+
+		if !unit.is_melee() {
+			let threats = enemies
+				.iter()
+				.filter(|e| e.can_attack_unit(unit) && unit.in_real_range_of(e, self.safety_gap))
+				.collect::<Vec<_>>();
+			if !threats.is_empty() && (!ready || !unit.can_attack()) {
+				return MicroCommand::Retreat(self.retreat_position(unit, &threats));
+			}
+		}
+
+		if in_range.is_some() {
+			return MicroCommand::Wait;
+		}
+		match self.best_target(unit, enemies) {
+			Some(target) if unit.can_attack() => MicroCommand::Advance(target.position()),
+			_ => MicroCommand::Wait,
+		}
+	}
+
+	/// Returns position away from given threats, where unit can step back to.
+	pub fn retreat_position(&self, unit: &Unit, threats: &[&Unit]) -> Point2 {
+		let pos = unit.position();
+		let away = threats
+			.iter()
+			.map(|t| {
+				let dir = pos - t.position();
+				if dir.len_squared() > 0.0 {
+					dir.normalize()
+				} else {
+					dir
+				}
+			})
+			.sum::<Point2>();
+
+		if away.len_squared() < f32::EPSILON {
+			return pos;
+		}
+		pos + away.normalize() * unit.distance_to_weapon_ready().max(1.0)
+	}
+}
@@ -0,0 +1,310 @@
+//! Manager of terran's orbital command energy.
+//!
+//! Bot keeps [`OrbitalManager`] in [`orbitals`](crate::bot::Bot::orbitals) field.
+//! Other parts of the bot request scans through it, and [`Bot::use_orbitals`]
+//! spends energy on requested scans, emergency supply drops and MULEs:
+//! ```
+//! use rust_sc2::{orbital::ScanTarget, prelude::*};
+//!
+//! #[bot]
+//! #[derive(Default)]
+//! struct MyBot;
+//! impl MyBot {
+//!     fn on_step(&mut self) {
+//!         let target = self.enemy_start;
+//!         if self.time > 240.0 && self.staleness(target).unwrap_or(u32::MAX) > 2000 {
+//!             self.orbitals.request_scan(ScanTarget::Pos(target), 1);
+//!         }
+//!         self.request_scan_cloaked(10);
+//!         self.use_orbitals();
+//!     }
+//! }
+//! ```
+
+use crate::{
+	action::Target,
+	bot::Bot,
+	distance::*,
+	game_state::Alliance,
+	geometry::Point2,
+	ids::{AbilityId, BuffId, EffectId, UnitTypeId},
+	unit::Unit,
+	units::Units,
+};
+use std::{cmp::Reverse, mem};
+
+/// Energy cost of MULE, scan and supply drop.
+const ORBITAL_ENERGY_COST: u32 = 50;
+/// Radius of scanner sweep.
+const SCAN_RADIUS: f32 = 13.0;
+/// Distance to owned units, at which hidden enemies are worth scanning.
+const SCAN_THREAT_DISTANCE: f32 = 10.0;
+/// Enemy effects created by units, which can't be seen without detection.
+const CLOAKED_EFFECTS: &[EffectId] = &[EffectId::LurkerMP];
+
+/// What should be revealed with scan.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ScanTarget {
+	/// Given position.
+	Pos(Point2),
+	/// Unit with given tag, e.g. cloaked enemy.
+	Unit(u64),
+}
+
+/// Request to reveal something with scanner sweep.
+#[derive(Debug, Copy, Clone)]
+pub struct ScanRequest {
+	/// What should be revealed.
+	pub target: ScanTarget,
+	/// Requests with higher priority are served first.
+	pub priority: u32,
+	/// Game loop when request was made.
+	pub requested: u32,
+}
+
+/// Manager of orbital commands' energy and scan requests.
+#[derive(Debug, Clone)]
+pub struct OrbitalManager {
+	/// Amount of scans energy is saved for (MULEs are called only on energy above it). [Default: `1`]
+	pub scan_reserve: u32,
+	/// Calldown supply drop when supply left is at most this value,
+	/// no supply depots are in progress and supply cap isn't maxed.
+	/// `None` disables supply drops. [Default: `Some(1)`]
+	pub supply_drop_threshold: Option<u32>,
+	/// Call MULEs on mineral fields. [Default: `true`]
+	pub mules: bool,
+	/// Game loops after which scan request is discarded, if it wasn't served. [Default: `224`]
+	pub request_timeout: u32,
+	requests: Vec<ScanRequest>,
+	game_loop: u32,
+}
+impl Default for OrbitalManager {
+	fn default() -> Self {
+		Self {
+			scan_reserve: 1,
+			supply_drop_threshold: Some(1),
+			mules: true,
+			request_timeout: 224,
+			requests: Vec::new(),
+			game_loop: 0,
+		}
+	}
+}
+impl OrbitalManager {
+	/// Requests to reveal given target with given priority.
+	/// If the same target was already requested, its priority is raised.
+	pub fn request_scan(&mut self, target: ScanTarget, priority: u32) {
+		match self.requests.iter_mut().find(|r| r.target == target) {
+			Some(request) => request.priority = request.priority.max(priority),
+			None => self.requests.push(ScanRequest {
+				target,
+				priority,
+				requested: self.game_loop,
+			}),
+		}
+	}
+	/// Scan requests waiting to be served.
+	pub fn requests(&self) -> &[ScanRequest] {
+		&self.requests
+	}
+	/// Discards all scan requests.
+	pub fn clear_requests(&mut self) {
+		self.requests.clear();
+	}
+
+	pub(crate) fn update(&mut self, game_loop: u32) {
+		self.game_loop = game_loop;
+		let timeout = self.request_timeout;
+		self.requests.retain(|r| game_loop - r.requested < timeout);
+	}
+}
+
+impl Bot {
+	/// Ready orbital commands, which aren't flying.
+	pub fn orbital_commands(&self) -> Units {
+		self.units
+			.my
+			.townhalls
+			.filter(|u| u.type_id() == UnitTypeId::OrbitalCommand && u.is_ready())
+	}
+	/// Total energy of orbital commands.
+	pub fn orbital_energy(&self) -> u32 {
+		self.orbital_commands().sum(|u| u.energy().unwrap_or(0))
+	}
+	/// Amount of scans orbital commands have energy for.
+	pub fn available_scans(&self) -> u32 {
+		self.orbital_commands()
+			.sum(|u| u.energy().unwrap_or(0) / ORBITAL_ENERGY_COST)
+	}
+
+	/// Requests scans of hidden enemies close to your units.
+	///
+	/// Scans are requested for:
+	/// - Cloaked or burrowed enemies, which aren't detected.
+	/// - Radar blips, i.e. enemies seen only by sensor tower.
+	/// - Effects of cloaked enemies (e.g. spines of burrowed lurker) with no detected source nearby.
+	pub fn request_scan_cloaked(&mut self, priority: u32) {
+		let units = &self.units.my.units;
+		let enemies = &self.units.enemy.all;
+		let is_threat = |e: &Unit| {
+			units
+				.iter()
+				.any(|u| u.can_attack_unit(e) && u.is_closer(SCAN_THREAT_DISTANCE, e))
+		};
+
+		let mut targets = enemies
+			.iter()
+			.filter(|e| e.is_cloaked() && !e.is_revealed() && is_threat(e))
+			.map(|e| ScanTarget::Unit(e.tag()))
+			.collect::<Vec<_>>();
+		targets.extend(
+			enemies
+				.iter()
+				.filter(|e| e.is_blip() && units.iter().any(|u| u.is_closer(SCAN_THREAT_DISTANCE, *e)))
+				.map(|e| ScanTarget::Pos(e.position())),
+		);
+		targets.extend(
+			self.state
+				.observation
+				.raw
+				.effects
+				.iter()
+				.filter(|e| e.alliance == Alliance::Enemy && CLOAKED_EFFECTS.contains(&e.id))
+				.filter_map(|e| {
+					e.positions
+						.iter()
+						.find(|p| units.iter().any(|u| u.is_closer(SCAN_THREAT_DISTANCE, **p)))
+						.copied()
+				})
+				.filter(|p| {
+					!enemies
+						.iter()
+						.any(|e| e.is_cloaked() && e.is_revealed() && e.is_closer(SCAN_RADIUS, *p))
+				})
+				.map(ScanTarget::Pos),
+		);
+		for target in targets {
+			self.orbitals.request_scan(target, priority);
+		}
+	}
+
+	/// Spends energy of orbital commands.
+	///
+	/// Energy is used in order:
+	/// 1. Requested scans. Requests close to each other are served with one scan,
+	///    and requests covered by active scans are considered served.
+	/// 2. Emergency supply drop on supply depot.
+	/// 3. MULE on the richest mineral field of owned expansions,
+	///    keeping energy for [`scan_reserve`](OrbitalManager::scan_reserve) scans.
+	pub fn use_orbitals(&mut self) {
+		let mut orbitals = self
+			.orbital_commands()
+			.into_iter()
+			.filter_map(|u| Some((u.energy()?, u)))
+			.collect::<Vec<(u32, Unit)>>();
+		let mut scans = self
+			.state
+			.observation
+			.raw
+			.effects
+			.iter()
+			.filter(|e| e.id == EffectId::ScannerSweep && e.alliance == Alliance::Own)
+			.flat_map(|e| e.positions.iter().copied())
+			.collect::<Vec<Point2>>();
+
+		// Scans
+		let mut requests = mem::take(&mut self.orbitals.requests);
+		requests.sort_by_key(|r| Reverse(r.priority));
+		requests.retain(|r| {
+			let pos = match r.target {
+				ScanTarget::Pos(pos) => pos,
+				ScanTarget::Unit(tag) => match self.units.enemy.all.get(tag) {
+					Some(u) if u.is_cloaked() && !u.is_revealed() => u.position(),
+					_ => return false,
+				},
+			};
+			if scans.iter().any(|s| s.is_closer(SCAN_RADIUS, pos)) {
+				return false;
+			}
+			match take_energy(&mut orbitals, AbilityId::ScannerSweepScan, 0) {
+				Some(orbital) => {
+					orbital.command(AbilityId::ScannerSweepScan, Target::Pos(pos), false);
+					scans.push(pos);
+					false
+				}
+				None => true,
+			}
+		});
+		self.orbitals.requests = requests;
+
+		// Supply drop
+		if let Some(threshold) = self.orbitals.supply_drop_threshold {
+			if self.supply_left <= threshold
+				&& self.supply_cap < 200
+				&& self.counter().ordered().count(UnitTypeId::SupplyDepot) == 0
+			{
+				let depot = self
+					.units
+					.my
+					.structures
+					.of_types(&vec![UnitTypeId::SupplyDepot, UnitTypeId::SupplyDepotLowered])
+					.ready()
+					.into_iter()
+					.find(|d| !d.has_buff(BuffId::SupplyDrop));
+				if let Some(depot) = depot {
+					if let Some(orbital) = take_energy(&mut orbitals, AbilityId::SupplyDropSupplyDrop, 0) {
+						orbital.command(AbilityId::SupplyDropSupplyDrop, Target::Tag(depot.tag()), false);
+					}
+				}
+			}
+		}
+
+		// MULEs
+		if !self.orbitals.mules {
+			return;
+		}
+		let reserve = self.orbitals.scan_reserve * ORBITAL_ENERGY_COST;
+		let mined = self
+			.units
+			.my
+			.units
+			.of_type(UnitTypeId::MULE)
+			.iter()
+			.filter_map(|u| u.target_tag())
+			.collect::<Vec<_>>();
+		let mut minerals = self
+			.owned_expansions()
+			.filter(|exp| matches!(exp.base, Some(base) if self.units.my.townhalls.contains_tag(base)))
+			.flat_map(|exp| exp.minerals.iter())
+			.filter(|tag| !mined.contains(tag))
+			.filter_map(|tag| self.units.mineral_fields.get(*tag))
+			.collect::<Vec<_>>();
+		minerals.sort_by_key(|m| Reverse(m.mineral_contents().unwrap_or(0)));
+
+		for mineral in minerals {
+			match take_energy(&mut orbitals, AbilityId::CalldownMULECalldownMULE, reserve) {
+				Some(orbital) => orbital.command(
+					AbilityId::CalldownMULECalldownMULE,
+					Target::Tag(mineral.tag()),
+					false,
+				),
+				None => break,
+			}
+		}
+	}
+}
+
+/// Takes orbital with the most energy, which can use given ability,
+/// keeping `reserve` energy in total among all orbitals.
+fn take_energy(orbitals: &mut [(u32, Unit)], ability: AbilityId, reserve: u32) -> Option<Unit> {
+	let total = orbitals.iter().map(|(energy, _)| *energy).sum::<u32>();
+	if total < reserve + ORBITAL_ENERGY_COST {
+		return None;
+	}
+	let (energy, orbital) = orbitals
+		.iter_mut()
+		.filter(|(energy, u)| *energy >= ORBITAL_ENERGY_COST && u.can_cast(ability))
+		.max_by_key(|(energy, _)| *energy)?;
+	*energy -= ORBITAL_ENERGY_COST;
+	Some(orbital.clone())
+}
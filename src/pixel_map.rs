@@ -5,6 +5,8 @@ use crate::{geometry::Point2, FromProto};
 use ndarray::Array2;
 use num_traits::FromPrimitive;
 use sc2_proto::common::ImageData;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use std::{
 	fmt,
 	ops::{Index, IndexMut},
@@ -16,6 +18,9 @@ pub type PixelMap = Array2<Pixel>;
 pub type ByteMap = Array2<u8>;
 /// 2-Dimensional Array that represents visibility.
 pub type VisibilityMap = Array2<Visibility>;
+/// 2-Dimensional Array of game loops, when each position was visible last time.
+/// `None` if position was never visible.
+pub type LastSeenMap = Array2<Option<u32>>;
 
 impl<T> Index<Point2> for Array2<T> {
 	type Output = T;
@@ -79,6 +84,7 @@ impl FromProto<&ImageData> for VisibilityMap {
 /// Base for the most 2d maps.
 #[variant_checkers]
 #[derive(FromPrimitive, ToPrimitive, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Pixel {
 	/// When pixel is set, this tile is obstacle (e.g. not pathable | not placeable)
 	/// or has something on it (e.g. has creep).
@@ -103,6 +109,7 @@ impl fmt::Debug for Pixel {
 /// Base for visibility maps.
 #[variant_checkers]
 #[derive(Debug, FromPrimitive, ToPrimitive, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Visibility {
 	/// Position is hidden (i.e. weren't explored before)
 	Hidden,
@@ -146,6 +146,7 @@ impl Default for AIBuild {
 
 /// Type of the player, used when joining a game.
 #[derive(Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum PlayerType {
 	/// Bot or Human.
 	Participant,
@@ -0,0 +1,182 @@
+//! Registry of roles claimed for owned units.
+//!
+//! Helps different parts of the bot not to fight over the same units.
+//! Registry is stored in [`roles`](crate::bot::Bot::roles) field of bot,
+//! claims are released automatically when unit dies or stays idle for too long.
+//! ```
+//! use rust_sc2::{prelude::*, roles::Role};
+//!
+//! #[bot]
+//! #[derive(Default)]
+//! struct MyBot;
+//! impl MyBot {
+//!     fn send_scout(&mut self) {
+//!         let target = self.enemy_start;
+//!         if let Some(scout) = self.free_workers().closest(target).cloned() {
+//!             if self.roles.claim(scout.tag(), Role::Scout, 10) {
+//!                 scout.move_to(Target::Pos(target), false);
+//!             }
+//!         }
+//!     }
+//! }
+//! ```
+
+use crate::{bot::Bot, units::Units};
+use rustc_hash::FxHashMap;
+
+/// Purpose unit is used for.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Role {
+	/// Gathering resources.
+	Gather,
+	/// Constructing structures.
+	Build,
+	/// Scouting opponent.
+	Scout,
+	/// Part of the main army.
+	Army,
+	/// Defending bases.
+	Defend,
+	/// Harassing opponent.
+	Harass,
+	/// Transporting other units.
+	Transport,
+	/// Any other purpose, distinguished by given id.
+	Custom(u32),
+}
+
+/// Information about claimed unit.
+#[derive(Debug, Copy, Clone)]
+pub struct Claim {
+	/// Role unit is claimed for.
+	pub role: Role,
+	/// Claims with higher priority can take unit from claims with lower one.
+	pub priority: u32,
+	/// Game loop when unit was claimed.
+	pub since: u32,
+	/// Game loop since which unit is idle, `None` if it's busy.
+	pub idle_since: Option<u32>,
+}
+
+/// Registry of unit roles.
+#[derive(Default, Clone)]
+pub struct Roles {
+	claims: FxHashMap<u64, Claim>,
+	idle_timeouts: FxHashMap<Role, u32>,
+	default_idle_timeout: Option<u32>,
+	game_loop: u32,
+}
+impl Roles {
+	/// Claims unit with given tag for given role.
+	///
+	/// Succeeds if unit is free or claimed with lower priority, returns `false` otherwise.
+	pub fn claim(&mut self, tag: u64, role: Role, priority: u32) -> bool {
+		if !self.can_claim(tag, priority) {
+			return false;
+		}
+		self.claims.insert(
+			tag,
+			Claim {
+				role,
+				priority,
+				since: self.game_loop,
+				idle_since: None,
+			},
+		);
+		true
+	}
+	/// Checks if unit with given tag can be claimed with given priority.
+	pub fn can_claim(&self, tag: u64, priority: u32) -> bool {
+		!matches!(self.claims.get(&tag), Some(c) if c.priority >= priority)
+	}
+	/// Releases unit with given tag, returns its previous claim.
+	pub fn release(&mut self, tag: u64) -> Option<Claim> {
+		self.claims.remove(&tag)
+	}
+	/// Releases all units claimed for given role.
+	pub fn release_role(&mut self, role: Role) {
+		self.claims.retain(|_, c| c.role != role);
+	}
+
+	/// Returns claim of unit with given tag.
+	pub fn claim_of(&self, tag: u64) -> Option<&Claim> {
+		self.claims.get(&tag)
+	}
+	/// Returns role of unit with given tag.
+	pub fn role_of(&self, tag: u64) -> Option<Role> {
+		self.claims.get(&tag).map(|c| c.role)
+	}
+	/// Checks if unit with given tag is claimed by anyone.
+	pub fn is_claimed(&self, tag: u64) -> bool {
+		self.claims.contains_key(&tag)
+	}
+	/// Returns tags of units claimed for given role.
+	pub fn claimed(&self, role: Role) -> impl Iterator<Item = u64> + '_ {
+		self.claims
+			.iter()
+			.filter(move |(_, c)| c.role == role)
+			.map(|(tag, _)| *tag)
+	}
+
+	/// Leaves only units not claimed by anyone.
+	pub fn unclaimed(&self, units: &Units) -> Units {
+		units.filter(|u| !self.is_claimed(u.tag()))
+	}
+	/// Leaves only units claimed for given role.
+	pub fn with_role(&self, units: &Units, role: Role) -> Units {
+		units.filter(|u| self.role_of(u.tag()) == Some(role))
+	}
+	/// Leaves only units which can be claimed with given priority.
+	pub fn claimable(&self, units: &Units, priority: u32) -> Units {
+		units.filter(|u| self.can_claim(u.tag(), priority))
+	}
+
+	/// Units claimed for given role are released after being idle for given amount of game loops.
+	/// `None` disables timeout for the role.
+	pub fn set_idle_timeout(&mut self, role: Role, timeout: Option<u32>) {
+		match timeout {
+			Some(timeout) => self.idle_timeouts.insert(role, timeout),
+			None => self.idle_timeouts.remove(&role),
+		};
+	}
+	/// Idle timeout used for roles without their own timeout. [Default: `None`]
+	pub fn set_default_idle_timeout(&mut self, timeout: Option<u32>) {
+		self.default_idle_timeout = timeout;
+	}
+
+	pub(crate) fn update(&mut self, units: &Units, game_loop: u32) {
+		self.game_loop = game_loop;
+
+		let idle_timeouts = &self.idle_timeouts;
+		let default_idle_timeout = self.default_idle_timeout;
+		self.claims.retain(|tag, claim| {
+			// Units in transports or refineries are not visible, but still alive
+			let unit = match units.get(*tag) {
+				Some(unit) => unit,
+				None => return true,
+			};
+
+			if !unit.is_idle() {
+				claim.idle_since = None;
+				return true;
+			}
+			let idle_since = *claim.idle_since.get_or_insert(game_loop);
+
+			match idle_timeouts.get(&claim.role).copied().or(default_idle_timeout) {
+				Some(timeout) => game_loop - idle_since < timeout,
+				None => true,
+			}
+		});
+	}
+}
+
+impl Bot {
+	/// Returns owned workers which aren't claimed by anyone.
+	pub fn free_workers(&self) -> Units {
+		self.roles.unclaimed(&self.units.my.workers)
+	}
+	/// Returns owned units (not structures) which aren't claimed by anyone.
+	pub fn free_units(&self) -> Units {
+		self.roles.unclaimed(&self.units.my.units)
+	}
+}
@@ -0,0 +1,467 @@
+//! Reproducible micro fights using Debug API.
+//!
+//! [`Scenario`] describes two armies, which are spawned at chosen points
+//! with [`create_units`](crate::debug::Debugger::create_units) and fight until one side dies.
+//! Between rounds arena is reset with [`kill_units`](crate::debug::Debugger::kill_units),
+//! so many rounds can be played on one map, and results are aggregated into [`ScenarioStats`].
+//!
+//! Whole map is revealed with [`show_map`](crate::debug::Debugger::show_map) before the first round,
+//! so both armies are tracked wherever they are (don't toggle it yourself).
+//! Unit is considered dead only when the game reports its death,
+//! so units leaving vision, cloaking or burrowing are still counted as alive.
+//!
+//! Put scenario to [`scenario`](crate::bot::Bot::scenario) field
+//! and call [`Bot::run_scenario`] every step, your micro code controls own army as usual:
+//! ```
+//! use rust_sc2::{prelude::*, scenario::*};
+//!
+//! #[bot]
+//! #[derive(Default)]
+//! struct MyBot;
+//! impl MyBot {
+//!     fn on_start(&mut self) {
+//!         let center = self.game_info.map_center;
+//!         self.scenario = Some(
+//!             Scenario::new(
+//!                 Army::new(center.offset(-5.0, 0.0)).unit(UnitTypeId::Marine, 10),
+//!                 Army::new(center.offset(5.0, 0.0)).unit(UnitTypeId::Zergling, 20),
+//!             )
+//!             .rounds(10),
+//!         );
+//!     }
+//!     fn on_step(&mut self) {
+//!         self.run_scenario();
+//!         if let Some(scenario) = &self.scenario {
+//!             if scenario.is_finished() {
+//!                 let stats = scenario.stats();
+//!                 println!("Win rate: {:.2}", stats.win_rate());
+//!             }
+//!         }
+//!     }
+//! }
+//! ```
+
+use crate::{bot::Bot, debug::UnitValue, geometry::Point2, ids::UnitTypeId};
+use rustc_hash::{FxHashMap, FxHashSet};
+
+/// Game loops to wait for spawned or killed units to appear in observation.
+const SYNC_TIMEOUT: u32 = 44;
+
+/// Army spawned at the start of each round.
+#[derive(Debug, Clone)]
+pub struct Army {
+	/// Position where army is spawned.
+	pub pos: Point2,
+	/// Types of units and their amounts.
+	pub units: Vec<(UnitTypeId, u32)>,
+	/// Health set to all units of army.
+	pub health: Option<u32>,
+	/// Shields set to all units of army.
+	pub shield: Option<u32>,
+	/// Energy set to all units of army.
+	pub energy: Option<u32>,
+}
+impl Army {
+	/// Constructs new army without units, spawned at given position.
+	pub fn new(pos: Point2) -> Self {
+		Self {
+			pos,
+			units: Vec::new(),
+			health: None,
+			shield: None,
+			energy: None,
+		}
+	}
+	/// Adds given amount of units of given type.
+	pub fn unit(mut self, unit: UnitTypeId, count: u32) -> Self {
+		self.units.push((unit, count));
+		self
+	}
+	/// Sets health of all units.
+	pub fn health(mut self, health: u32) -> Self {
+		self.health = Some(health);
+		self
+	}
+	/// Sets shields of all units.
+	pub fn shield(mut self, shield: u32) -> Self {
+		self.shield = Some(shield);
+		self
+	}
+	/// Sets energy of all units.
+	pub fn energy(mut self, energy: u32) -> Self {
+		self.energy = Some(energy);
+		self
+	}
+
+	fn size(&self) -> usize {
+		self.units.iter().map(|(_, count)| *count as usize).sum()
+	}
+	fn values(&self, tags: &FxHashSet<u64>) -> Vec<(u64, UnitValue, u32)> {
+		let values = [
+			(UnitValue::Health, self.health),
+			(UnitValue::Shield, self.shield),
+			(UnitValue::Energy, self.energy),
+		];
+		tags.iter()
+			.flat_map(|tag| {
+				values
+					.iter()
+					.filter_map(move |(kind, value)| Some((*tag, *kind, (*value)?)))
+			})
+			.collect()
+	}
+}
+
+/// Outcome of a round.
+#[variant_checkers]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Outcome {
+	/// Enemy army died.
+	Win,
+	/// Own army died.
+	Loss,
+	/// Both armies died or round timed out.
+	Draw,
+}
+
+/// Result of a single round.
+#[derive(Debug, Copy, Clone)]
+pub struct RoundResult {
+	/// Who won the round.
+	pub outcome: Outcome,
+	/// Duration of fight in game loops.
+	pub duration: u32,
+	/// Minerals lost by own army.
+	pub minerals_lost: u32,
+	/// Vespene lost by own army.
+	pub vespene_lost: u32,
+	/// Minerals lost by enemy army.
+	pub enemy_minerals_lost: u32,
+	/// Vespene lost by enemy army.
+	pub enemy_vespene_lost: u32,
+}
+
+/// Results of all played rounds.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct ScenarioStats {
+	/// Amount of played rounds.
+	pub rounds: u32,
+	/// Amount of won rounds.
+	pub wins: u32,
+	/// Amount of lost rounds.
+	pub losses: u32,
+	/// Amount of draws.
+	pub draws: u32,
+	/// Total minerals lost by own armies.
+	pub minerals_lost: u32,
+	/// Total vespene lost by own armies.
+	pub vespene_lost: u32,
+	/// Total minerals lost by enemy armies.
+	pub enemy_minerals_lost: u32,
+	/// Total vespene lost by enemy armies.
+	pub enemy_vespene_lost: u32,
+}
+impl ScenarioStats {
+	/// Share of won rounds, `0` if no rounds were played.
+	pub fn win_rate(&self) -> f32 {
+		if self.rounds == 0 {
+			0.0
+		} else {
+			self.wins as f32 / self.rounds as f32
+		}
+	}
+	/// Own resources lost per round on average.
+	pub fn avg_lost(&self) -> f32 {
+		self.per_round(self.minerals_lost + self.vespene_lost)
+	}
+	/// Enemy resources lost per round on average.
+	pub fn avg_enemy_lost(&self) -> f32 {
+		self.per_round(self.enemy_minerals_lost + self.enemy_vespene_lost)
+	}
+	fn per_round(&self, value: u32) -> f32 {
+		if self.rounds == 0 {
+			0.0
+		} else {
+			value as f32 / self.rounds as f32
+		}
+	}
+}
+
+/// Stage of current round.
+#[variant_checkers]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum RoundState {
+	/// Units of previous round are being killed.
+	Resetting,
+	/// Armies are being spawned.
+	Spawning,
+	/// Armies are fighting.
+	Fighting,
+	/// All rounds are played.
+	Finished,
+}
+
+/// Fight of two armies repeated for given number of rounds.
+#[derive(Debug, Clone)]
+pub struct Scenario {
+	/// Own army.
+	pub army: Army,
+	/// Enemy army.
+	pub enemy: Army,
+	/// Number of rounds to play. [Default: `1`]
+	pub rounds: u32,
+	/// Maximum duration of fight in game loops, after which round is a draw. [Default: `2688`]
+	pub round_timeout: u32,
+	/// Grant all upgrades with [`cheat_upgrades`](crate::debug::Debugger::cheat_upgrades)
+	/// before first round. [Default: `false`]
+	pub upgrades: bool,
+	/// End game after all rounds are played. [Default: `false`]
+	pub end_game: bool,
+	state: RoundState,
+	since: u32,
+	known: FxHashSet<u64>,
+	my_tags: FxHashSet<u64>,
+	enemy_tags: FxHashSet<u64>,
+	types: FxHashMap<u64, UnitTypeId>,
+	dead: FxHashSet<u64>,
+	pending_kills: FxHashSet<u64>,
+	results: Vec<RoundResult>,
+}
+impl Scenario {
+	/// Constructs new scenario of given own and enemy armies.
+	pub fn new(army: Army, enemy: Army) -> Self {
+		Self {
+			army,
+			enemy,
+			rounds: 1,
+			round_timeout: 2688,
+			upgrades: false,
+			end_game: false,
+			state: RoundState::Resetting,
+			since: 0,
+			known: Default::default(),
+			my_tags: Default::default(),
+			enemy_tags: Default::default(),
+			types: Default::default(),
+			dead: Default::default(),
+			pending_kills: Default::default(),
+			results: Vec::new(),
+		}
+	}
+	/// Sets number of rounds.
+	pub fn rounds(mut self, rounds: u32) -> Self {
+		self.rounds = rounds;
+		self
+	}
+	/// Sets maximum duration of fight in game loops.
+	pub fn round_timeout(mut self, loops: u32) -> Self {
+		self.round_timeout = loops;
+		self
+	}
+	/// Grants all upgrades before first round.
+	pub fn upgrades(mut self) -> Self {
+		self.upgrades = true;
+		self
+	}
+	/// Ends game after all rounds are played.
+	pub fn end_game(mut self) -> Self {
+		self.end_game = true;
+		self
+	}
+
+	/// Stage of current round.
+	pub fn state(&self) -> RoundState {
+		self.state
+	}
+	/// Checks if armies are fighting now.
+	pub fn is_fighting(&self) -> bool {
+		self.state.is_fighting()
+	}
+	/// Checks if all rounds are played.
+	pub fn is_finished(&self) -> bool {
+		self.state.is_finished()
+	}
+	/// Tags of own army in current round.
+	pub fn army_tags(&self) -> &FxHashSet<u64> {
+		&self.my_tags
+	}
+	/// Tags of enemy army in current round.
+	pub fn enemy_tags(&self) -> &FxHashSet<u64> {
+		&self.enemy_tags
+	}
+	/// Results of played rounds.
+	pub fn results(&self) -> &[RoundResult] {
+		&self.results
+	}
+	/// Aggregated results of played rounds.
+	pub fn stats(&self) -> ScenarioStats {
+		let mut stats = ScenarioStats::default();
+		for r in &self.results {
+			stats.rounds += 1;
+			match r.outcome {
+				Outcome::Win => stats.wins += 1,
+				Outcome::Loss => stats.losses += 1,
+				Outcome::Draw => stats.draws += 1,
+			}
+			stats.minerals_lost += r.minerals_lost;
+			stats.vespene_lost += r.vespene_lost;
+			stats.enemy_minerals_lost += r.enemy_minerals_lost;
+			stats.enemy_vespene_lost += r.enemy_vespene_lost;
+		}
+		stats
+	}
+}
+
+impl Bot {
+	/// Advances [`scenario`](Bot::scenario): resets arena, spawns armies,
+	/// sets their values and records results of fights.
+	pub fn run_scenario(&mut self) {
+		let mut scenario = match self.scenario.take() {
+			Some(scenario) => scenario,
+			None => return,
+		};
+		self.step_scenario(&mut scenario);
+		self.scenario = Some(scenario);
+	}
+
+	fn step_scenario(&mut self, s: &mut Scenario) {
+		let game_loop = self.state.observation.game_loop();
+		let dead_units = &self.state.observation.raw.dead_units;
+
+		match s.state {
+			RoundState::Resetting => {
+				s.pending_kills.retain(|tag| !dead_units.contains(tag));
+				if !s.pending_kills.is_empty() && game_loop - s.since < SYNC_TIMEOUT {
+					return;
+				}
+				s.pending_kills.clear();
+				if s.results.is_empty() {
+					if s.upgrades {
+						self.debug.cheat_upgrades();
+					}
+					self.debug.show_map();
+				}
+				let owners = [(&s.army, self.player_id), (&s.enemy, self.enemy_player_id)];
+				for (army, owner) in owners {
+					let cmds = army
+						.units
+						.iter()
+						.map(|(unit, count)| (*unit, Some(owner), army.pos, *count))
+						.collect::<Vec<_>>();
+					self.debug.create_units(&cmds);
+				}
+				s.known = self.units.all.iter().map(|u| u.tag()).collect();
+				s.my_tags.clear();
+				s.enemy_tags.clear();
+				s.types.clear();
+				s.dead.clear();
+				s.state = RoundState::Spawning;
+				s.since = game_loop;
+			}
+			RoundState::Spawning => {
+				for (tags, units) in [
+					(&mut s.my_tags, &self.units.my.all),
+					(&mut s.enemy_tags, &self.units.enemy.all),
+				] {
+					for u in units.iter().filter(|u| !s.known.contains(&u.tag())) {
+						tags.insert(u.tag());
+						s.types.insert(u.tag(), u.type_id());
+					}
+				}
+				let spawned = s.my_tags.len() >= s.army.size() && s.enemy_tags.len() >= s.enemy.size();
+				if !spawned && game_loop - s.since < SYNC_TIMEOUT {
+					return;
+				}
+				let mut values = s.army.values(&s.my_tags);
+				values.extend(s.enemy.values(&s.enemy_tags));
+				self.debug.set_unit_values(&values);
+				s.state = RoundState::Fighting;
+				s.since = game_loop;
+			}
+			RoundState::Fighting => {
+				s.dead.extend(
+					dead_units
+						.iter()
+						.filter(|tag| s.my_tags.contains(tag) || s.enemy_tags.contains(tag)),
+				);
+				let dead = &s.dead;
+				let army = s
+					.my_tags
+					.iter()
+					.filter(|tag| !dead.contains(tag))
+					.copied()
+					.collect::<Vec<_>>();
+				let enemy = s
+					.enemy_tags
+					.iter()
+					.filter(|tag| !dead.contains(tag))
+					.copied()
+					.collect::<Vec<_>>();
+				let duration = game_loop - s.since;
+				let outcome = match (army.is_empty(), enemy.is_empty()) {
+					(false, true) => Outcome::Win,
+					(true, false) => Outcome::Loss,
+					(true, true) => Outcome::Draw,
+					(false, false) if duration >= s.round_timeout => Outcome::Draw,
+					_ => return,
+				};
+
+				let (minerals_lost, vespene_lost) = self.scenario_losses(&s.army, &army, &s.types);
+				let (enemy_minerals_lost, enemy_vespene_lost) =
+					self.scenario_losses(&s.enemy, &enemy, &s.types);
+				s.results.push(RoundResult {
+					outcome,
+					duration,
+					minerals_lost,
+					vespene_lost,
+					enemy_minerals_lost,
+					enemy_vespene_lost,
+				});
+
+				// Survivors are killed, previous round is over when game reports their deaths
+				s.my_tags.clear();
+				s.enemy_tags.clear();
+				s.dead.clear();
+				let survivors = army.into_iter().chain(enemy).collect::<Vec<_>>();
+				self.debug.kill_units(&survivors);
+
+				if s.results.len() as u32 >= s.rounds {
+					s.state = RoundState::Finished;
+					if s.end_game {
+						self.debug.end_game();
+					}
+				} else {
+					s.pending_kills = survivors.into_iter().collect();
+					s.state = RoundState::Resetting;
+					s.since = game_loop;
+				}
+			}
+			RoundState::Finished => {}
+		}
+	}
+
+	/// Resources lost by army: full API cost of spawned units minus cost of survivors.
+	fn scenario_losses(
+		&self,
+		army: &Army,
+		survivors: &[u64],
+		types: &FxHashMap<u64, UnitTypeId>,
+	) -> (u32, u32) {
+		let spawned = army
+			.units
+			.iter()
+			.fold((0, 0), |(minerals, vespene), (unit, count)| {
+				let cost = self.get_unit_api_cost(*unit);
+				(minerals + cost.minerals * count, vespene + cost.vespene * count)
+			});
+		let left =
+			survivors
+				.iter()
+				.filter_map(|tag| types.get(tag))
+				.fold((0, 0), |(minerals, vespene), unit| {
+					let cost = self.get_unit_api_cost(*unit);
+					(minerals + cost.minerals, vespene + cost.vespene)
+				});
+		(spawned.0.saturating_sub(left.0), spawned.1.saturating_sub(left.1))
+	}
+}
@@ -0,0 +1,201 @@
+//! Scouting planner built on top of [`last_seen`](Bot::last_seen) history map.
+//!
+//! Ranks interesting locations by how long they weren't visible and how important they are:
+//! ```
+//! use rust_sc2::prelude::*;
+//!
+//! #[bot]
+//! #[derive(Default)]
+//! struct MyBot;
+//! impl MyBot {
+//!     fn scout(&mut self) {
+//!         if let Some(scout) = self.units.my.workers.first() {
+//!             let route = self.scout_route(scout, 3);
+//!             for (i, pos) in route.into_iter().enumerate() {
+//!                 scout.move_to(Target::Pos(pos), i > 0);
+//!             }
+//!         }
+//!     }
+//! }
+//! ```
+
+use crate::{bot::Bot, distance::*, geometry::Point2, unit::Unit};
+
+/// Additional distance kept from enemy units when building scouting routes.
+const THREAT_GAP: f32 = 1.5;
+/// Time in seconds after which proxies aren't likely to be built.
+const PROXY_TIME: f32 = 300.0;
+
+/// Kind of location to scout.
+#[variant_checkers]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ScoutTargetKind {
+	/// Opponent's start location.
+	EnemyMain,
+	/// Expansion occupied by opponent or free one on opponent's half of the map.
+	Expansion,
+	/// Free expansion on your half of the map, which is likely to be used for proxy.
+	Proxy,
+}
+
+/// Location to scout, returned by [`scout_targets`](Bot::scout_targets).
+#[derive(Debug, Copy, Clone)]
+pub struct ScoutTarget {
+	/// Position to scout.
+	pub pos: Point2,
+	/// Kind of location.
+	pub kind: ScoutTargetKind,
+	/// How important the location is.
+	pub importance: f32,
+	/// Game loops passed since location was visible last time.
+	/// Never visible locations count from the start of the game.
+	pub staleness: u32,
+	/// Final rank of location, equals to `importance * staleness`.
+	pub score: f32,
+}
+
+impl Bot {
+	/// Returns locations worth scouting, sorted by score in descending order.
+	///
+	/// Considers opponent's start location, [`expansions`](Self::expansions)
+	/// not owned by you and likely proxy locations.
+	pub fn scout_targets(&self) -> Vec<ScoutTarget> {
+		let game_loop = self.state.observation.game_loop();
+		let proxy_importance = if self.time < PROXY_TIME { 1.5 } else { 0.5 };
+
+		let mut targets = Vec::new();
+		let mut add_target = |pos: Point2, kind: ScoutTargetKind, importance: f32| {
+			let staleness = self.staleness(pos).unwrap_or(game_loop + 1);
+			targets.push(ScoutTarget {
+				pos,
+				kind,
+				importance,
+				staleness,
+				score: importance * staleness as f32,
+			});
+		};
+
+		add_target(self.enemy_start, ScoutTargetKind::EnemyMain, 3.0);
+		for exp in &self.expansions {
+			if exp.alliance.is_mine() || exp.loc.is_closer(5.0, self.enemy_start) {
+				continue;
+			}
+
+			if exp.alliance.is_enemy() {
+				add_target(exp.loc, ScoutTargetKind::Expansion, 2.0);
+				continue;
+			}
+
+			let to_me = exp.loc.distance(self.start_location);
+			let to_enemy = exp.loc.distance(self.enemy_start);
+			if to_me < to_enemy {
+				add_target(exp.loc, ScoutTargetKind::Proxy, proxy_importance);
+			} else {
+				add_target(
+					exp.loc,
+					ScoutTargetKind::Expansion,
+					0.5 + to_me / (to_me + to_enemy),
+				);
+			}
+		}
+
+		targets.sort_unstable_by(|a, b| b.score.partial_cmp(&a.score).unwrap());
+		targets
+	}
+
+	/// Returns known enemy units, which can attack given unit, as circles of `(center, radius)`.
+	pub fn threats_to(&self, unit: &Unit) -> Vec<(Point2, f32)> {
+		({
+			#[cfg(not(feature = "enemies_cache"))]
+			{
+				&self.units.enemy.all
+			}
+			#[cfg(feature = "enemies_cache")]
+			{
+				&self.units.cached.all
+			}
+		})
+		.iter()
+		.filter(|e| e.can_attack_unit(unit))
+		.map(|e| (e.position(), e.real_range_vs(unit) + THREAT_GAP))
+		.collect()
+	}
+
+	/// Builds patrol route for given scout through at most `max_targets` most stale
+	/// [`scout_targets`](Self::scout_targets).
+	///
+	/// Targets covered by known threats are skipped,
+	/// and detour waypoints are inserted to go around threats on the way.
+	pub fn scout_route(&self, scout: &Unit, max_targets: usize) -> Vec<Point2> {
+		let threats = self.threats_to(scout);
+		let is_safe = |pos: Point2| threats.iter().all(|(c, r)| pos.is_further(*r, *c));
+
+		let mut targets = self
+			.scout_targets()
+			.into_iter()
+			.filter(|t| t.score > 0.0 && is_safe(t.pos))
+			.take(max_targets)
+			.map(|t| t.pos)
+			.collect::<Vec<_>>();
+
+		let mut route = Vec::with_capacity(targets.len());
+		let mut current = scout.position();
+		while !targets.is_empty() {
+			let (i, _) = targets
+				.iter()
+				.enumerate()
+				.min_by(|(_, a), (_, b)| {
+					a.distance_squared(current)
+						.partial_cmp(&b.distance_squared(current))
+						.unwrap()
+				})
+				.unwrap();
+			let next = targets.swap_remove(i);
+
+			route.extend(self.detours(scout, current, next, &threats));
+			route.push(next);
+			current = next;
+		}
+		route
+	}
+
+	pub(crate) fn detours(
+		&self,
+		scout: &Unit,
+		from: Point2,
+		to: Point2,
+		threats: &[(Point2, f32)],
+	) -> Vec<Point2> {
+		let dir = to - from;
+		let len_squared = dir.len_squared();
+		if len_squared == 0.0 {
+			return Vec::new();
+		}
+
+		let mut detours = threats
+			.iter()
+			.filter_map(|(center, radius)| {
+				let t = ((*center - from).dot(dir) / len_squared).clamp(0.0, 1.0);
+				let closest = from + dir * t;
+				if closest.is_further(*radius, *center) {
+					return None;
+				}
+
+				let away = closest - *center;
+				let normal = if away.len_squared() > 0.0 {
+					away.normalize()
+				} else {
+					dir.rotate90(true).normalize()
+				};
+				let offset = radius + 1.0;
+				let waypoint = [*center + normal * offset, *center - normal * offset]
+					.into_iter()
+					.find(|p| scout.is_flying() || self.is_pathable(*p))?;
+				Some((t, waypoint))
+			})
+			.collect::<Vec<_>>();
+
+		detours.sort_unstable_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap());
+		detours.into_iter().map(|(_, p)| p).collect()
+	}
+}
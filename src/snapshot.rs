@@ -0,0 +1,274 @@
+//! Snapshots of the bot state, which can be saved and loaded back without running game.
+//!
+//! With `serde` feature enabled [`Snapshot`] can be serialized with any serde format,
+//! so a single frame can be saved to disk, attached to bug report and loaded back in tests:
+//! ```
+//! use rust_sc2::{bot::Bot, snapshot::Snapshot};
+//!
+//! fn check(snapshot: Snapshot) {
+//!     let bot = Bot::from_snapshot(snapshot);
+//!     assert!(bot.units.my.townhalls.len() <= bot.units.my.structures.len());
+//! }
+//! ```
+//!
+//! Saving snapshot of current step (e.g. with `serde_json`):
+//! ```ignore
+//! let file = std::fs::File::create("snapshot.json")?;
+//! serde_json::to_writer(file, &self.snapshot())?;
+//! ```
+//!
+//! Loading it back:
+//! ```ignore
+//! let file = std::fs::File::open("snapshot.json")?;
+//! let bot = Bot::from_snapshot(serde_json::from_reader(file)?);
+//! ```
+//!
+//! Snapshots can also be assembled by hand, so bot's logic can be tested in plain `#[test]`s:
+//! ```
+//! use rust_sc2::{prelude::*, snapshot::Snapshot};
+//!
+//! let bot = Snapshot::new(Race::Terran, 64, 64)
+//!     .units([
+//!         Unit::builder(UnitTypeId::Marine).pos(Point2::new(20.0, 20.0)).build(),
+//!         Unit::builder(UnitTypeId::Zergling)
+//!             .pos(Point2::new(22.0, 20.0))
+//!             .alliance(Alliance::Enemy)
+//!             .build(),
+//!     ])
+//!     .minerals(100)
+//!     .bot();
+//!
+//! let marine = bot.units.my.units.first().unwrap();
+//! assert_eq!(bot.units.enemy.units.in_range_of(marine, 0.0).len(), 1);
+//! assert!(bot.can_afford(UnitTypeId::Marine, false));
+//! ```
+
+use crate::{
+	bot::{Bot, Expansion, LockU32, Locked, Rl, Rs},
+	game_data::GameData,
+	game_info::{GameInfo, PlayerInfo},
+	game_state::GameState,
+	geometry::Point2,
+	ids::{AbilityId, UpgradeId},
+	pixel_map::{PixelMap, Visibility, VisibilityMap},
+	player::{PlayerType, Race},
+	ramp::Ramps,
+	unit::Unit,
+	units::Units,
+};
+use rustc_hash::{FxHashMap, FxHashSet};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Single frame of the game as bot sees it.
+///
+/// Made with [`Bot::snapshot`] and restored with [`Bot::from_snapshot`].
+#[derive(Default, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Snapshot {
+	/// Bot's in-game id.
+	pub player_id: u32,
+	/// Opponent's in-game id.
+	pub enemy_player_id: u32,
+	/// Actual race of the bot.
+	pub race: Race,
+	/// Requested race of the opponent.
+	pub enemy_race: Race,
+	/// Step between every `on_step` iteration.
+	pub game_step: u32,
+	/// Information about map.
+	pub game_info: GameInfo,
+	/// Constant information about abilities, unit types, upgrades, buffs and effects.
+	pub game_data: GameData,
+	/// Information about current state.
+	pub state: GameState,
+	/// All units bot sees on this step.
+	pub units: Units,
+	/// Abilities available to owned units, mapped by their tags.
+	pub abilities_units: FxHashMap<u64, FxHashSet<AbilityId>>,
+	/// Opponent's upgrades known by bot.
+	pub enemy_upgrades: FxHashSet<UpgradeId>,
+	/// All expansions.
+	pub expansions: Vec<Expansion>,
+	/// Ramps on map.
+	pub ramps: Ramps,
+	/// Obstacles on map which block vision of ground units, but still pathable.
+	pub vision_blockers: Vec<Point2>,
+	/// Bot's starting location.
+	pub start_location: Point2,
+	/// Opponent's starting location.
+	pub enemy_start: Point2,
+	/// Bot's resource center on start location.
+	pub start_center: Point2,
+	/// Opponents's resource center on start location.
+	pub enemy_start_center: Point2,
+}
+
+impl Snapshot {
+	/// Empty snapshot of the first step on [flat map](GameInfo::flat) of given size,
+	/// where everything is visible.
+	///
+	/// Bot has id `1` and opponent has id `2`.
+	pub fn new(race: Race, width: usize, height: usize) -> Self {
+		let mut game_info = GameInfo::flat(width, height);
+		for (id, race, race_actual) in [(1, race, Some(race)), (2, Race::Random, None)] {
+			game_info.players.insert(
+				id,
+				PlayerInfo {
+					id,
+					player_type: PlayerType::Participant,
+					race_requested: race,
+					race_actual,
+					difficulty: None,
+					ai_build: None,
+					player_name: None,
+				},
+			);
+		}
+
+		let mut state = GameState::default();
+		let obs = &mut state.observation;
+		obs.common.player_id = 1;
+		obs.raw.visibility = VisibilityMap::from_elem((width, height), Visibility::Visible);
+		obs.raw.creep = Rs::new(Rl::new(PixelMap::default((width, height))));
+
+		Self {
+			player_id: 1,
+			enemy_player_id: 2,
+			race,
+			enemy_race: Race::Random,
+			game_step: 1,
+			game_info,
+			state,
+			..Default::default()
+		}
+	}
+	/// Adds units to the snapshot, their alliance defines to whom they belong.
+	pub fn units<I: IntoIterator<Item = Unit>>(mut self, units: I) -> Self {
+		self.units.extend(units);
+		self
+	}
+	/// Sets game data, by default it's empty and units fall back to [static data](crate::static_data).
+	pub fn game_data(mut self, game_data: GameData) -> Self {
+		self.game_data = game_data;
+		self
+	}
+	/// Sets amount of minerals bot has.
+	pub fn minerals(mut self, minerals: u32) -> Self {
+		self.state.observation.common.minerals = minerals;
+		self
+	}
+	/// Sets amount of vespene bot has.
+	pub fn vespene(mut self, vespene: u32) -> Self {
+		self.state.observation.common.vespene = vespene;
+		self
+	}
+	/// Sets used supply and supply capacity.
+	pub fn supply(mut self, used: u32, cap: u32) -> Self {
+		let common = &mut self.state.observation.common;
+		common.food_used = used;
+		common.food_cap = cap;
+		self
+	}
+	/// Sets current game loop.
+	pub fn game_loop(mut self, game_loop: u32) -> Self {
+		self.state.observation.game_loop = Rs::new(LockU32::new(game_loop));
+		self
+	}
+	/// Adds bot's ready upgrades.
+	pub fn upgrades<I: IntoIterator<Item = UpgradeId>>(self, upgrades: I) -> Self {
+		self.state.observation.raw.upgrades.write_lock().extend(upgrades);
+		self
+	}
+	/// Constructs bot from the snapshot, shortcut for [`Bot::from_snapshot`].
+	pub fn bot(self) -> Bot {
+		Bot::from_snapshot(self)
+	}
+}
+
+impl Bot {
+	/// Makes snapshot of current step.
+	///
+	/// Only state received from the game and calculated on start is saved,
+	/// state of bot's subsystems (squads, roles, planners, ...) isn't included.
+	pub fn snapshot(&self) -> Snapshot {
+		let mut state = self.state.clone();
+		// Detaching shared state from the bot
+		let obs = &mut state.observation;
+		obs.game_loop = Rs::new(LockU32::new(obs.game_loop()));
+		let upgrades = obs.raw.upgrades.read_lock().clone();
+		obs.raw.upgrades = Rs::new(Rl::new(upgrades));
+		let creep = obs.raw.creep.read_lock().clone();
+		obs.raw.creep = Rs::new(Rl::new(creep));
+
+		Snapshot {
+			player_id: self.player_id,
+			enemy_player_id: self.enemy_player_id,
+			race: self.race,
+			enemy_race: self.enemy_race,
+			game_step: self.game_step(),
+			game_info: self.game_info.clone(),
+			game_data: (*self.game_data).clone(),
+			state,
+			units: self.units.all.clone(),
+			abilities_units: self.abilities_units.read_lock().clone(),
+			enemy_upgrades: self.enemy_upgrades.read_lock().clone(),
+			expansions: self.expansions.clone(),
+			ramps: self.ramps.clone(),
+			vision_blockers: self.vision_blockers.clone(),
+			start_location: self.start_location,
+			enemy_start: self.enemy_start,
+			start_center: self.start_center,
+			enemy_start_center: self.enemy_start_center,
+		}
+	}
+	/// Constructs bot from the snapshot, as if it received that step from the game.
+	///
+	/// Bot isn't connected to the game, so it can't send requests (i.e. queries),
+	/// but commands given to units are collected as usual.
+	pub fn from_snapshot(snapshot: Snapshot) -> Self {
+		let mut bot = Self::default();
+		bot.player_id = snapshot.player_id;
+		bot.enemy_player_id = snapshot.enemy_player_id;
+		bot.race = snapshot.race;
+		bot.enemy_race = snapshot.enemy_race;
+		bot.game_info = snapshot.game_info;
+		bot.game_data = Rs::new(snapshot.game_data);
+		bot.state = snapshot.state;
+		bot.expansions = snapshot.expansions;
+		bot.vision_blockers = snapshot.vision_blockers;
+		bot.start_location = snapshot.start_location;
+		bot.enemy_start = snapshot.enemy_start;
+		bot.start_center = snapshot.start_center;
+		bot.enemy_start_center = snapshot.enemy_start_center;
+		bot.set_game_step(snapshot.game_step.max(1));
+		*bot.abilities_units.write_lock() = snapshot.abilities_units;
+		*bot.enemy_upgrades() = snapshot.enemy_upgrades;
+		bot.link_data_for_unit();
+
+		// Ramps and units are restored without data shared by the bot
+		let height = &bot.game_info.terrain_height;
+		let ramps = &snapshot.ramps;
+		bot.ramps = Ramps {
+			all: ramps.all.iter().map(|r| r.with_height(height)).collect(),
+			my: ramps.my.with_height(height),
+			enemy: ramps.enemy.with_height(height),
+		};
+		let units = snapshot
+			.units
+			.iter()
+			.map(|u| u.with_data(&bot.data_for_unit))
+			.collect();
+		bot.update_units(units);
+
+		// Units seen in snapshot shouldn't trigger events on the next step
+		for u in &bot.units.my.all {
+			bot.owned_tags.insert(u.tag());
+			if u.is_structure() && !u.is_ready() {
+				bot.under_construction.insert(u.tag());
+			}
+		}
+		bot.prepare_step();
+		bot
+	}
+}
@@ -0,0 +1,291 @@
+//! Grouping owned units into named squads.
+//!
+//! Squads are stored in [`squads`](crate::bot::Bot::squads) field of bot.
+//! Dead units are removed from squads automatically and
+//! new units are assigned to squads using [`RallyRule`]s:
+//! ```
+//! use rust_sc2::{prelude::*, squads::RallyRule};
+//!
+//! #[bot]
+//! #[derive(Default)]
+//! struct MyBot;
+//! impl MyBot {
+//!     fn on_start(&mut self) {
+//!         self.squads.add_rule(RallyRule::new("harass").types(vec![UnitTypeId::Reaper]).limit(2));
+//!         self.squads.add_rule(RallyRule::new("main"));
+//!     }
+//!     fn on_step(&mut self) {
+//!         let target = self.enemy_start;
+//!         if let Some(main) = self.squads.get("main") {
+//!             if main.len() > 20 {
+//!                 main.attack_move(target);
+//!             } else if main.spread() > 10.0 {
+//!                 main.regroup();
+//!             }
+//!         }
+//!     }
+//! }
+//! ```
+
+use crate::{
+	action::Target, distance::Distance, geometry::Point2, ids::UnitTypeId, unit::Unit, units::Units,
+};
+use rustc_hash::FxHashMap;
+
+/// Named group of owned units.
+#[derive(Default, Clone)]
+pub struct Squad {
+	name: String,
+	tags: Vec<u64>,
+	units: Units,
+}
+impl Squad {
+	fn new(name: String) -> Self {
+		Self {
+			name,
+			..Default::default()
+		}
+	}
+
+	/// Name of the squad.
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+	/// Tags of all squad members, including those which are not visible now (e.g. in transport).
+	pub fn tags(&self) -> &[u64] {
+		&self.tags
+	}
+	/// Currently visible squad members.
+	pub fn units(&self) -> &Units {
+		&self.units
+	}
+	/// Checks if unit with given tag is a member of the squad.
+	pub fn contains(&self, tag: u64) -> bool {
+		self.tags.contains(&tag)
+	}
+	/// Amount of squad members.
+	pub fn len(&self) -> usize {
+		self.tags.len()
+	}
+	/// Checks if squad has no members.
+	pub fn is_empty(&self) -> bool {
+		self.tags.is_empty()
+	}
+
+	/// Central position of visible members or `None` if there're no such.
+	pub fn center(&self) -> Option<Point2> {
+		self.units.center()
+	}
+	/// Average distance of visible members to the squad's center.
+	pub fn spread(&self) -> f32 {
+		match self.center() {
+			Some(center) => self.units.sum(|u| u.position().distance(center)) / self.units.len() as f32,
+			None => 0.0,
+		}
+	}
+	/// Total resource cost (minerals + vespene) of visible members.
+	pub fn value(&self) -> u32 {
+		self.units.sum(|u| {
+			let cost = u.cost();
+			cost.minerals + cost.vespene
+		})
+	}
+	/// Movement speed of the slowest visible member, `0` if squad is empty.
+	pub fn slowest_speed(&self) -> f32 {
+		self.units.min_value(|u| u.speed()).unwrap_or(0.0)
+	}
+	/// Combined ground dps of visible members, without considering upgrades.
+	pub fn ground_dps(&self) -> f32 {
+		self.units.sum(|u| u.ground_dps())
+	}
+	/// Combined air dps of visible members, without considering upgrades.
+	pub fn air_dps(&self) -> f32 {
+		self.units.sum(|u| u.air_dps())
+	}
+	/// Combined dps of visible members vs given target, without considering upgrades.
+	pub fn dps_vs(&self, target: &Unit) -> f32 {
+		self.units.sum(|u| u.dps_vs(target))
+	}
+
+	/// Orders all visible members to attack-move to given position.
+	pub fn attack_move(&self, pos: Point2) {
+		for u in &self.units {
+			u.attack(Target::Pos(pos), false);
+		}
+	}
+	/// Orders all visible members to move to the squad's center.
+	pub fn regroup(&self) {
+		if let Some(center) = self.center() {
+			self.move_to(center);
+		}
+	}
+	/// Orders all visible members to move to given position (e.g. to your base).
+	pub fn retreat(&self, pos: Point2) {
+		self.move_to(pos);
+	}
+	fn move_to(&self, pos: Point2) {
+		for u in &self.units {
+			u.move_to(Target::Pos(pos), false);
+		}
+	}
+}
+
+/// Rule used to assign newly created units to squads.
+#[derive(Debug, Clone)]
+pub struct RallyRule {
+	/// Name of the squad units will be assigned to.
+	pub squad: String,
+	/// Unit types accepted by the rule, if empty accepts any non-worker unit.
+	/// Larvae and eggs are never accepted, units hatched from them are rallied instead.
+	pub types: Vec<UnitTypeId>,
+	/// Maximum amount of members squad can have to accept new units.
+	pub limit: Option<usize>,
+}
+impl RallyRule {
+	/// Constructs rule which accepts any non-worker unit.
+	pub fn new<S: Into<String>>(squad: S) -> Self {
+		Self {
+			squad: squad.into(),
+			types: Vec::new(),
+			limit: None,
+		}
+	}
+	/// Sets unit types accepted by the rule.
+	pub fn types(mut self, types: Vec<UnitTypeId>) -> Self {
+		self.types = types;
+		self
+	}
+	/// Sets maximum size of the squad.
+	pub fn limit(mut self, limit: usize) -> Self {
+		self.limit = Some(limit);
+		self
+	}
+
+	fn accepts(&self, unit: &Unit) -> bool {
+		if is_unhatched(unit.type_id()) {
+			false
+		} else if self.types.is_empty() {
+			!unit.is_worker()
+		} else {
+			self.types.contains(&unit.type_id())
+		}
+	}
+}
+
+/// All squads of the bot and rules to fill them.
+#[derive(Default, Clone)]
+pub struct Squads {
+	squads: FxHashMap<String, Squad>,
+	membership: FxHashMap<u64, String>,
+	rules: Vec<RallyRule>,
+	/// Larvae and eggs waiting to hatch with their last seen type.
+	unhatched: FxHashMap<u64, UnitTypeId>,
+}
+impl Squads {
+	/// Returns squad with given name, creating it if it doesn't exist yet.
+	pub fn create(&mut self, name: &str) -> &Squad {
+		self.get_or_create(name)
+	}
+	fn get_or_create(&mut self, name: &str) -> &mut Squad {
+		self.squads
+			.entry(name.to_string())
+			.or_insert_with(|| Squad::new(name.to_string()))
+	}
+	/// Returns squad with given name.
+	pub fn get(&self, name: &str) -> Option<&Squad> {
+		self.squads.get(name)
+	}
+	/// Disbands squad with given name, its members become unassigned.
+	pub fn remove(&mut self, name: &str) -> Option<Squad> {
+		let squad = self.squads.remove(name)?;
+		for tag in &squad.tags {
+			self.membership.remove(tag);
+		}
+		Some(squad)
+	}
+	/// Iterates over all squads.
+	pub fn iter(&self) -> impl Iterator<Item = &Squad> {
+		self.squads.values()
+	}
+	/// Returns squad which unit with given tag belongs to.
+	pub fn squad_of(&self, tag: u64) -> Option<&Squad> {
+		self.squads.get(self.membership.get(&tag)?)
+	}
+
+	/// Assigns unit to squad with given name, creating squad if needed.
+	/// If unit was in other squad, it's moved from there.
+	pub fn assign(&mut self, unit: &Unit, name: &str) {
+		self.unassign(unit.tag());
+		let squad = self.get_or_create(name);
+		squad.tags.push(unit.tag());
+		squad.units.push(unit.clone());
+		self.membership.insert(unit.tag(), name.to_string());
+	}
+	/// Removes unit with given tag from its squad.
+	/// If it's a larva or an egg, unit won't be rallied when it hatches.
+	pub fn unassign(&mut self, tag: u64) {
+		self.unhatched.remove(&tag);
+		if let Some(name) = self.membership.remove(&tag) {
+			if let Some(squad) = self.squads.get_mut(&name) {
+				squad.tags.retain(|t| *t != tag);
+				squad.units.remove(tag);
+			}
+		}
+	}
+
+	/// Adds rule for new units. Rules are checked in order they were added.
+	pub fn add_rule(&mut self, rule: RallyRule) {
+		self.rules.push(rule);
+	}
+	/// Removes all rally rules.
+	pub fn clear_rules(&mut self) {
+		self.rules.clear();
+	}
+	/// Assigns unit to squad of the first matching rally rule.
+	/// Returns `true` if unit was assigned.
+	///
+	/// Larvae and eggs aren't assigned, instead they are rallied again when hatched.
+	pub fn rally(&mut self, unit: &Unit) -> bool {
+		if is_unhatched(unit.type_id()) {
+			self.unhatched.insert(unit.tag(), unit.type_id());
+			return false;
+		}
+		self.unhatched.remove(&unit.tag());
+
+		let squads = &self.squads;
+		let name = self
+			.rules
+			.iter()
+			.find(|rule| {
+				rule.accepts(unit)
+					&& !matches!(
+						rule.limit,
+						Some(limit) if squads.get(&rule.squad).map_or(0, |s| s.len()) >= limit
+					)
+			})
+			.map(|rule| rule.squad.clone());
+
+		match name {
+			Some(name) => {
+				self.assign(unit, &name);
+				true
+			}
+			None => false,
+		}
+	}
+
+	/// Checks if larva or egg has changed its type since it was rallied last time.
+	pub(crate) fn is_hatched(&self, unit: &Unit) -> bool {
+		matches!(self.unhatched.get(&unit.tag()), Some(type_id) if *type_id != unit.type_id())
+	}
+
+	pub(crate) fn update(&mut self, units: &Units) {
+		for squad in self.squads.values_mut() {
+			squad.units = squad.tags.iter().filter_map(|t| units.get(*t)).cloned().collect();
+		}
+	}
+}
+
+fn is_unhatched(type_id: UnitTypeId) -> bool {
+	matches!(type_id, UnitTypeId::Larva | UnitTypeId::Egg)
+}
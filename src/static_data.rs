@@ -0,0 +1,965 @@
+//! Static data about units, upgrades and abilities, which doesn't require running game.
+//!
+//! Tables are maintained by hand, values are taken from Liquipedia unit and upgrade pages
+//! for melee balance of patch 5.0.11. `static-data` example runs the game and prints entries,
+//! which differ from values reported by the API, it should be used to update tables after balance patches.
+//!
+//! Values use the same units of measurement as [`GameData`](crate::game_data::GameData) does:
+//! - build and research times are in game loops;
+//! - movement speed is in distance per second on **Normal** game speed;
+//! - weapon cooldown is in seconds on **Normal** game speed.
+//!
+//! Costs are reported the same way as the API does (i.e. morphs include cost of the morphed unit
+//! and zerg buildings include cost of the drone), so [`Bot::get_unit_cost`] works with them as is.
+//!
+//! [`Bot`] and [`Unit`] fall back to these tables when [`GameData`] lacks an entry.
+//! They can be also used directly in offline tools like planners and simulators:
+//! ```
+//! use rust_sc2::{ids::UnitTypeId, static_data::unit_info};
+//!
+//! let marine = unit_info(UnitTypeId::Marine).unwrap();
+//! assert_eq!(marine.health, 45);
+//! assert_eq!(marine.cost().minerals, 50);
+//! ```
+//!
+//! [`Bot`]: crate::bot::Bot
+//! [`Bot::get_unit_cost`]: crate::bot::Bot::get_unit_cost
+//! [`Unit`]: crate::unit::Unit
+//! [`GameData`]: crate::game_data::GameData
+
+use crate::{
+	consts::{ABILITY_COOLDOWNS, ABILITY_ENERGY_COSTS, GAME_SPEED, UNIT_ALIAS},
+	game_data::{Attribute, Cost, TargetType, Weapon},
+	ids::{AbilityId, UnitTypeId, UpgradeId},
+	player::Race,
+};
+use std::collections::HashMap;
+
+use Attribute::*;
+
+/// Static information about unit type.
+#[derive(Clone)]
+pub struct UnitInfo {
+	pub race: Race,
+	pub mineral_cost: u32,
+	pub vespene_cost: u32,
+	pub food_required: f32,
+	pub food_provided: f32,
+	/// Build time in game loops.
+	pub build_time: f32,
+	pub health: u32,
+	pub shield: u32,
+	pub armor: i32,
+	pub attributes: Vec<Attribute>,
+	pub weapons: Vec<Weapon>,
+	/// Radius of unit.
+	pub radius: f32,
+	/// Size of building in tiles or `None` for units.
+	pub building_size: Option<usize>,
+	/// Movement speed on **Normal** game speed.
+	pub movement_speed: f32,
+	pub sight_range: f32,
+	/// Space usage in transports and bunkers.
+	pub cargo_size: u32,
+	/// Unit is an air unit or a lifted building.
+	pub is_flying: bool,
+}
+impl UnitInfo {
+	pub fn cost(&self) -> Cost {
+		Cost {
+			minerals: self.mineral_cost,
+			vespene: self.vespene_cost,
+			supply: self.food_required,
+			time: self.build_time,
+		}
+	}
+
+	fn new(race: Race, minerals: u32, vespene: u32, supply: f32, build_time: f32) -> Self {
+		Self {
+			race,
+			mineral_cost: minerals,
+			vespene_cost: vespene,
+			food_required: supply,
+			food_provided: 0.0,
+			build_time,
+			health: 0,
+			shield: 0,
+			armor: 0,
+			attributes: vec![],
+			weapons: vec![],
+			radius: 0.0,
+			building_size: None,
+			movement_speed: 0.0,
+			sight_range: 0.0,
+			cargo_size: 0,
+			is_flying: false,
+		}
+	}
+	fn hp(mut self, health: u32, shield: u32, armor: i32) -> Self {
+		self.health = health;
+		self.shield = shield;
+		self.armor = armor;
+		self
+	}
+	fn attributes(mut self, attributes: &[Attribute]) -> Self {
+		self.attributes = attributes.to_vec();
+		self
+	}
+	/// Speed is given on **Faster** game speed, as it's usually listed.
+	fn unit(mut self, radius: f32, speed: f32, sight: f32, cargo: u32) -> Self {
+		self.radius = radius;
+		self.movement_speed = speed / GAME_SPEED;
+		self.sight_range = sight;
+		self.cargo_size = cargo;
+		self
+	}
+	fn flying(mut self) -> Self {
+		self.is_flying = true;
+		self
+	}
+	fn building(mut self, size: usize, sight: f32) -> Self {
+		self.radius = size as f32 / 2.0;
+		self.building_size = Some(size);
+		self.sight_range = sight;
+		self
+	}
+	fn provides(mut self, supply: f32) -> Self {
+		self.food_provided = supply;
+		self
+	}
+	fn weapon(mut self, weapon: Weapon) -> Self {
+		self.weapons.push(weapon);
+		self
+	}
+}
+
+/// Cooldown is given on **Faster** game speed, as it's usually listed.
+fn weapon(target: TargetType, damage: u32, attacks: u32, range: f32, cooldown: f32) -> Weapon {
+	Weapon {
+		target,
+		damage,
+		damage_bonus: vec![],
+		attacks,
+		range,
+		speed: cooldown * GAME_SPEED,
+	}
+}
+fn ground(damage: u32, attacks: u32, range: f32, cooldown: f32) -> Weapon {
+	weapon(TargetType::Ground, damage, attacks, range, cooldown)
+}
+fn air(damage: u32, attacks: u32, range: f32, cooldown: f32) -> Weapon {
+	weapon(TargetType::Air, damage, attacks, range, cooldown)
+}
+fn any(damage: u32, attacks: u32, range: f32, cooldown: f32) -> Weapon {
+	weapon(TargetType::Any, damage, attacks, range, cooldown)
+}
+fn bonus(mut weapon: Weapon, attribute: Attribute, damage: u32) -> Weapon {
+	weapon.damage_bonus.push((attribute, damage));
+	weapon
+}
+
+fn terran(minerals: u32, vespene: u32, supply: f32, build_time: f32) -> UnitInfo {
+	UnitInfo::new(Race::Terran, minerals, vespene, supply, build_time)
+}
+fn protoss(minerals: u32, vespene: u32, supply: f32, build_time: f32) -> UnitInfo {
+	UnitInfo::new(Race::Protoss, minerals, vespene, supply, build_time)
+}
+fn zerg(minerals: u32, vespene: u32, supply: f32, build_time: f32) -> UnitInfo {
+	UnitInfo::new(Race::Zerg, minerals, vespene, supply, build_time)
+}
+
+/// Static information about upgrade.
+#[derive(Clone)]
+pub struct UpgradeInfo {
+	pub mineral_cost: u32,
+	pub vespene_cost: u32,
+	/// Research time in game loops.
+	pub research_time: f32,
+}
+impl UpgradeInfo {
+	pub fn cost(&self) -> Cost {
+		Cost {
+			minerals: self.mineral_cost,
+			vespene: self.vespene_cost,
+			supply: 0.0,
+			time: self.research_time,
+		}
+	}
+}
+
+fn upgrade(minerals: u32, vespene: u32, research_time: f32) -> UpgradeInfo {
+	UpgradeInfo {
+		mineral_cost: minerals,
+		vespene_cost: vespene,
+		research_time,
+	}
+}
+
+/// Static information about ability.
+#[derive(Clone, Default)]
+pub struct AbilityInfo {
+	/// Maximum range to target of the ability.
+	pub cast_range: Option<f32>,
+	/// Cooldown of the ability in game loops.
+	pub cooldown: Option<u32>,
+	/// Energy needed to use the ability.
+	pub energy_cost: Option<u32>,
+}
+
+lazy_static! {
+	/// Static information about melee unit types.
+	pub static ref UNIT_INFO: HashMap<UnitTypeId, UnitInfo> = {
+		let mut units = hashmap![
+			// Terran units
+			UnitTypeId::SCV => terran(50, 0, 1.0, 272.0)
+				.hp(45, 0, 0)
+				.attributes(&[Light, Biological, Mechanical])
+				.unit(0.375, 3.94, 8.0, 1)
+				.weapon(ground(5, 1, 0.1, 1.07)),
+			UnitTypeId::MULE => terran(0, 0, 0.0, 0.0)
+				.hp(60, 0, 0)
+				.attributes(&[Light, Mechanical, Summoned])
+				.unit(0.375, 3.94, 8.0, 1),
+			UnitTypeId::Marine => terran(50, 0, 1.0, 400.0)
+				.hp(45, 0, 0)
+				.attributes(&[Light, Biological])
+				.unit(0.375, 3.15, 9.0, 1)
+				.weapon(any(6, 1, 5.0, 0.61)),
+			UnitTypeId::Marauder => terran(100, 25, 2.0, 480.0)
+				.hp(125, 0, 1)
+				.attributes(&[Armored, Biological])
+				.unit(0.5625, 3.15, 10.0, 2)
+				.weapon(bonus(ground(10, 1, 6.0, 1.07), Armored, 10)),
+			UnitTypeId::Reaper => terran(50, 50, 1.0, 720.0)
+				.hp(60, 0, 0)
+				.attributes(&[Light, Biological])
+				.unit(0.375, 5.25, 9.0, 1)
+				.weapon(ground(4, 2, 5.0, 0.79)),
+			UnitTypeId::Ghost => terran(150, 125, 2.0, 640.0)
+				.hp(100, 0, 0)
+				.attributes(&[Biological, Psionic])
+				.unit(0.375, 3.94, 11.0, 2)
+				.weapon(bonus(any(10, 1, 6.0, 1.07), Light, 10)),
+			UnitTypeId::Hellion => terran(100, 0, 2.0, 480.0)
+				.hp(90, 0, 0)
+				.attributes(&[Light, Mechanical])
+				.unit(0.625, 5.95, 10.0, 2)
+				.weapon(bonus(ground(8, 1, 5.0, 1.79), Light, 6)),
+			UnitTypeId::HellionTank => terran(100, 0, 2.0, 480.0)
+				.hp(135, 0, 0)
+				.attributes(&[Light, Biological, Mechanical])
+				.unit(0.625, 3.15, 10.0, 2)
+				.weapon(bonus(ground(18, 1, 2.0, 1.43), Light, 12)),
+			UnitTypeId::WidowMine => terran(75, 25, 2.0, 480.0)
+				.hp(90, 0, 0)
+				.attributes(&[Light, Mechanical])
+				.unit(0.5, 3.94, 7.0, 2),
+			UnitTypeId::WidowMineBurrowed => terran(75, 25, 2.0, 480.0)
+				.hp(90, 0, 0)
+				.attributes(&[Light, Mechanical])
+				.unit(0.5, 0.0, 7.0, 2)
+				.weapon(any(125, 1, 5.0, 29.0)),
+			UnitTypeId::SiegeTank => terran(150, 125, 3.0, 720.0)
+				.hp(175, 0, 1)
+				.attributes(&[Armored, Mechanical])
+				.unit(0.875, 3.15, 11.0, 4)
+				.weapon(bonus(ground(15, 1, 7.0, 1.04), Armored, 10)),
+			UnitTypeId::SiegeTankSieged => terran(150, 125, 3.0, 720.0)
+				.hp(175, 0, 1)
+				.attributes(&[Armored, Mechanical])
+				.unit(0.875, 0.0, 11.0, 4)
+				.weapon(bonus(ground(40, 1, 13.0, 2.14), Armored, 30)),
+			UnitTypeId::Cyclone => terran(150, 100, 3.0, 720.0)
+				.hp(120, 0, 1)
+				.attributes(&[Armored, Mechanical])
+				.unit(0.75, 4.13, 11.0, 4)
+				.weapon(any(18, 1, 5.0, 0.71)),
+			UnitTypeId::Thor => terran(300, 200, 6.0, 960.0)
+				.hp(400, 0, 1)
+				.attributes(&[Armored, Mechanical, Massive])
+				.unit(1.25, 2.62, 11.0, 8)
+				.weapon(ground(30, 2, 7.0, 0.91))
+				.weapon(bonus(air(6, 4, 10.0, 2.14), Light, 6)),
+			UnitTypeId::VikingFighter => terran(150, 75, 2.0, 672.0)
+				.hp(135, 0, 0)
+				.attributes(&[Armored, Mechanical])
+				.unit(0.75, 3.85, 10.0, 0)
+				.flying()
+				.weapon(bonus(air(10, 2, 9.0, 1.43), Armored, 4)),
+			UnitTypeId::VikingAssault => terran(150, 75, 2.0, 672.0)
+				.hp(135, 0, 0)
+				.attributes(&[Armored, Mechanical])
+				.unit(0.75, 3.15, 10.0, 2)
+				.weapon(bonus(ground(12, 1, 6.0, 0.71), Mechanical, 8)),
+			UnitTypeId::Medivac => terran(100, 100, 2.0, 672.0)
+				.hp(150, 0, 1)
+				.attributes(&[Armored, Mechanical])
+				.unit(0.75, 3.5, 11.0, 0)
+				.flying(),
+			UnitTypeId::Liberator => terran(150, 125, 3.0, 960.0)
+				.hp(180, 0, 0)
+				.attributes(&[Armored, Mechanical])
+				.unit(0.75, 4.72, 10.0, 0)
+				.flying()
+				.weapon(air(5, 2, 5.0, 1.29)),
+			UnitTypeId::LiberatorAG => terran(150, 125, 3.0, 960.0)
+				.hp(180, 0, 0)
+				.attributes(&[Armored, Mechanical])
+				.unit(0.75, 0.0, 10.0, 0)
+				.flying()
+				.weapon(ground(75, 1, 10.0, 1.14)),
+			UnitTypeId::Raven => terran(100, 150, 2.0, 768.0)
+				.hp(140, 0, 1)
+				.attributes(&[Light, Mechanical])
+				.unit(0.625, 3.85, 11.0, 0)
+				.flying(),
+			UnitTypeId::Banshee => terran(150, 100, 3.0, 960.0)
+				.hp(140, 0, 0)
+				.attributes(&[Light, Mechanical])
+				.unit(0.75, 3.85, 10.0, 0)
+				.flying()
+				.weapon(ground(12, 2, 6.0, 0.89)),
+			UnitTypeId::Battlecruiser => terran(400, 300, 6.0, 1440.0)
+				.hp(550, 0, 3)
+				.attributes(&[Armored, Mechanical, Massive])
+				.unit(1.25, 2.62, 12.0, 0)
+				.flying()
+				.weapon(ground(8, 1, 6.0, 0.16))
+				.weapon(air(5, 1, 6.0, 0.16)),
+			// Terran structures
+			UnitTypeId::CommandCenter => terran(400, 0, 0.0, 1600.0)
+				.hp(1500, 0, 1)
+				.attributes(&[Armored, Mechanical, Structure])
+				.building(5, 11.0)
+				.provides(15.0),
+			UnitTypeId::OrbitalCommand => terran(550, 0, 0.0, 560.0)
+				.hp(1500, 0, 1)
+				.attributes(&[Armored, Mechanical, Structure])
+				.building(5, 11.0)
+				.provides(15.0),
+			UnitTypeId::PlanetaryFortress => terran(550, 150, 0.0, 800.0)
+				.hp(1500, 0, 3)
+				.attributes(&[Armored, Mechanical, Structure])
+				.building(5, 11.0)
+				.provides(15.0)
+				.weapon(ground(40, 1, 6.0, 1.43)),
+			UnitTypeId::SupplyDepot => terran(100, 0, 0.0, 480.0)
+				.hp(400, 0, 1)
+				.attributes(&[Armored, Mechanical, Structure])
+				.building(2, 9.0)
+				.provides(8.0),
+			UnitTypeId::Refinery => terran(75, 0, 0.0, 480.0)
+				.hp(500, 0, 1)
+				.attributes(&[Armored, Mechanical, Structure])
+				.building(3, 9.0),
+			UnitTypeId::Barracks => terran(150, 0, 0.0, 1040.0)
+				.hp(1000, 0, 1)
+				.attributes(&[Armored, Mechanical, Structure])
+				.building(3, 9.0),
+			UnitTypeId::EngineeringBay => terran(125, 0, 0.0, 560.0)
+				.hp(850, 0, 1)
+				.attributes(&[Armored, Mechanical, Structure])
+				.building(3, 9.0),
+			UnitTypeId::Bunker => terran(100, 0, 0.0, 640.0)
+				.hp(400, 0, 1)
+				.attributes(&[Armored, Mechanical, Structure])
+				.building(3, 10.0),
+			UnitTypeId::MissileTurret => terran(100, 0, 0.0, 400.0)
+				.hp(250, 0, 0)
+				.attributes(&[Armored, Mechanical, Structure])
+				.building(2, 11.0)
+				.weapon(air(12, 2, 7.0, 0.61)),
+			UnitTypeId::SensorTower => terran(125, 100, 0.0, 400.0)
+				.hp(200, 0, 0)
+				.attributes(&[Armored, Mechanical, Structure])
+				.building(1, 12.0),
+			UnitTypeId::Factory => terran(150, 100, 0.0, 960.0)
+				.hp(1250, 0, 1)
+				.attributes(&[Armored, Mechanical, Structure])
+				.building(3, 9.0),
+			UnitTypeId::GhostAcademy => terran(150, 50, 0.0, 640.0)
+				.hp(1250, 0, 1)
+				.attributes(&[Armored, Mechanical, Structure])
+				.building(3, 9.0),
+			UnitTypeId::Armory => terran(150, 100, 0.0, 1040.0)
+				.hp(750, 0, 1)
+				.attributes(&[Armored, Mechanical, Structure])
+				.building(3, 9.0),
+			UnitTypeId::Starport => terran(150, 100, 0.0, 800.0)
+				.hp(1300, 0, 1)
+				.attributes(&[Armored, Mechanical, Structure])
+				.building(3, 9.0),
+			UnitTypeId::FusionCore => terran(150, 150, 0.0, 1040.0)
+				.hp(750, 0, 1)
+				.attributes(&[Armored, Mechanical, Structure])
+				.building(3, 9.0),
+			UnitTypeId::TechLab => terran(50, 25, 0.0, 400.0)
+				.hp(400, 0, 1)
+				.attributes(&[Armored, Mechanical, Structure])
+				.building(2, 9.0),
+			UnitTypeId::Reactor => terran(50, 50, 0.0, 800.0)
+				.hp(400, 0, 1)
+				.attributes(&[Armored, Mechanical, Structure])
+				.building(2, 9.0),
+			// Protoss units
+			UnitTypeId::Probe => protoss(50, 0, 1.0, 272.0)
+				.hp(20, 20, 0)
+				.attributes(&[Light, Mechanical])
+				.unit(0.375, 3.94, 8.0, 1)
+				.weapon(ground(5, 1, 0.1, 1.07)),
+			UnitTypeId::Zealot => protoss(100, 0, 2.0, 608.0)
+				.hp(100, 50, 1)
+				.attributes(&[Light, Biological])
+				.unit(0.5, 3.15, 9.0, 2)
+				.weapon(ground(8, 2, 0.1, 0.86)),
+			UnitTypeId::Stalker => protoss(125, 50, 2.0, 672.0)
+				.hp(80, 80, 1)
+				.attributes(&[Armored, Mechanical])
+				.unit(0.625, 4.13, 10.0, 2)
+				.weapon(bonus(any(13, 1, 6.0, 1.34), Armored, 5)),
+			UnitTypeId::Sentry => protoss(50, 100, 2.0, 592.0)
+				.hp(40, 40, 1)
+				.attributes(&[Light, Mechanical, Psionic])
+				.unit(0.5, 3.15, 10.0, 2)
+				.weapon(any(6, 1, 5.0, 0.71)),
+			UnitTypeId::Adept => protoss(100, 25, 2.0, 672.0)
+				.hp(70, 70, 1)
+				.attributes(&[Light, Biological])
+				.unit(0.5, 3.5, 9.0, 2)
+				.weapon(bonus(ground(10, 1, 4.0, 1.61), Light, 12)),
+			UnitTypeId::HighTemplar => protoss(50, 150, 2.0, 880.0)
+				.hp(40, 40, 0)
+				.attributes(&[Light, Biological, Psionic])
+				.unit(0.5, 2.62, 10.0, 2),
+			UnitTypeId::DarkTemplar => protoss(125, 125, 2.0, 880.0)
+				.hp(40, 80, 1)
+				.attributes(&[Light, Biological, Psionic])
+				.unit(0.5, 3.94, 8.0, 2)
+				.weapon(ground(45, 1, 0.1, 1.21)),
+			UnitTypeId::Archon => protoss(175, 275, 4.0, 272.0)
+				.hp(10, 350, 0)
+				.attributes(&[Psionic, Massive])
+				.unit(1.0, 3.94, 9.0, 4)
+				.weapon(bonus(any(25, 1, 3.0, 1.25), Biological, 10)),
+			UnitTypeId::Immortal => protoss(275, 100, 4.0, 880.0)
+				.hp(200, 100, 1)
+				.attributes(&[Armored, Mechanical])
+				.unit(0.75, 3.15, 9.0, 4)
+				.weapon(bonus(ground(20, 1, 6.0, 1.04), Armored, 30)),
+			UnitTypeId::Colossus => protoss(300, 200, 6.0, 1200.0)
+				.hp(200, 150, 1)
+				.attributes(&[Armored, Mechanical, Massive])
+				.unit(1.0, 3.15, 10.0, 8)
+				.weapon(bonus(ground(10, 2, 7.0, 1.07), Light, 5)),
+			UnitTypeId::Disruptor => protoss(150, 150, 4.0, 800.0)
+				.hp(100, 100, 1)
+				.attributes(&[Armored, Mechanical])
+				.unit(0.5, 3.15, 9.0, 4),
+			UnitTypeId::Observer => protoss(25, 75, 1.0, 480.0)
+				.hp(40, 20, 0)
+				.attributes(&[Light, Mechanical])
+				.unit(0.5, 2.63, 11.0, 0)
+				.flying(),
+			UnitTypeId::WarpPrism => protoss(250, 0, 2.0, 800.0)
+				.hp(80, 100, 0)
+				.attributes(&[Armored, Mechanical, Psionic])
+				.unit(0.875, 4.13, 10.0, 0)
+				.flying(),
+			UnitTypeId::Phoenix => protoss(150, 100, 2.0, 560.0)
+				.hp(120, 60, 0)
+				.attributes(&[Light, Mechanical])
+				.unit(0.75, 5.95, 10.0, 0)
+				.flying()
+				.weapon(bonus(air(5, 2, 5.0, 0.79), Light, 5)),
+			UnitTypeId::VoidRay => protoss(250, 150, 4.0, 960.0)
+				.hp(150, 100, 0)
+				.attributes(&[Armored, Mechanical])
+				.unit(1.0, 3.85, 10.0, 0)
+				.flying()
+				.weapon(bonus(any(6, 1, 6.0, 0.36), Armored, 4)),
+			UnitTypeId::Oracle => protoss(150, 150, 3.0, 832.0)
+				.hp(100, 60, 0)
+				.attributes(&[Armored, Mechanical, Psionic])
+				.unit(0.75, 5.6, 10.0, 0)
+				.flying()
+				.weapon(bonus(ground(15, 1, 4.0, 0.61), Light, 7)),
+			UnitTypeId::Tempest => protoss(250, 175, 5.0, 960.0)
+				.hp(200, 100, 2)
+				.attributes(&[Armored, Mechanical, Massive])
+				.unit(1.25, 3.15, 12.0, 0)
+				.flying()
+				.weapon(ground(40, 1, 10.0, 2.36))
+				.weapon(bonus(air(30, 1, 14.0, 2.36), Massive, 22)),
+			UnitTypeId::Carrier => protoss(350, 250, 6.0, 1440.0)
+				.hp(300, 150, 2)
+				.attributes(&[Armored, Mechanical, Massive])
+				.unit(1.25, 2.62, 12.0, 0)
+				.flying(),
+			UnitTypeId::Interceptor => protoss(15, 0, 0.0, 240.0)
+				.hp(40, 40, 0)
+				.attributes(&[Light, Mechanical])
+				.unit(0.25, 10.5, 7.0, 0)
+				.flying()
+				.weapon(any(5, 2, 2.0, 2.14)),
+			UnitTypeId::Mothership => protoss(400, 400, 8.0, 1760.0)
+				.hp(350, 350, 2)
+				.attributes(&[Armored, Mechanical, Psionic, Massive, Heroic])
+				.unit(1.375, 2.62, 14.0, 0)
+				.flying()
+				.weapon(any(6, 4, 7.0, 1.58)),
+			// Protoss structures
+			UnitTypeId::Nexus => protoss(400, 0, 0.0, 1600.0)
+				.hp(1000, 1000, 1)
+				.attributes(&[Armored, Structure])
+				.building(5, 11.0)
+				.provides(15.0),
+			UnitTypeId::Pylon => protoss(100, 0, 0.0, 400.0)
+				.hp(200, 200, 1)
+				.attributes(&[Armored, Structure])
+				.building(2, 9.0)
+				.provides(8.0),
+			UnitTypeId::Assimilator => protoss(75, 0, 0.0, 480.0)
+				.hp(450, 450, 1)
+				.attributes(&[Armored, Structure])
+				.building(3, 9.0),
+			UnitTypeId::Gateway => protoss(150, 0, 0.0, 1040.0)
+				.hp(500, 500, 1)
+				.attributes(&[Armored, Structure])
+				.building(3, 9.0),
+			UnitTypeId::WarpGate => protoss(150, 0, 0.0, 160.0)
+				.hp(500, 500, 1)
+				.attributes(&[Armored, Structure])
+				.building(3, 9.0),
+			UnitTypeId::Forge => protoss(150, 0, 0.0, 720.0)
+				.hp(400, 400, 1)
+				.attributes(&[Armored, Structure])
+				.building(3, 9.0),
+			UnitTypeId::CyberneticsCore => protoss(150, 0, 0.0, 800.0)
+				.hp(550, 550, 1)
+				.attributes(&[Armored, Structure])
+				.building(3, 9.0),
+			UnitTypeId::PhotonCannon => protoss(150, 0, 0.0, 640.0)
+				.hp(150, 150, 1)
+				.attributes(&[Armored, Structure])
+				.building(2, 11.0)
+				.weapon(any(20, 1, 7.0, 0.89)),
+			UnitTypeId::ShieldBattery => protoss(100, 0, 0.0, 640.0)
+				.hp(150, 150, 1)
+				.attributes(&[Armored, Structure])
+				.building(2, 9.0),
+			UnitTypeId::TwilightCouncil => protoss(150, 100, 0.0, 800.0)
+				.hp(500, 500, 1)
+				.attributes(&[Armored, Structure])
+				.building(3, 9.0),
+			UnitTypeId::RoboticsFacility => protoss(150, 100, 0.0, 1040.0)
+				.hp(450, 450, 1)
+				.attributes(&[Armored, Structure])
+				.building(3, 9.0),
+			UnitTypeId::Stargate => protoss(150, 150, 0.0, 960.0)
+				.hp(600, 600, 1)
+				.attributes(&[Armored, Structure])
+				.building(3, 9.0),
+			UnitTypeId::TemplarArchive => protoss(150, 200, 0.0, 800.0)
+				.hp(500, 500, 1)
+				.attributes(&[Armored, Structure])
+				.building(3, 9.0),
+			UnitTypeId::DarkShrine => protoss(150, 150, 0.0, 1600.0)
+				.hp(500, 500, 1)
+				.attributes(&[Armored, Structure])
+				.building(2, 9.0),
+			UnitTypeId::RoboticsBay => protoss(150, 150, 0.0, 1040.0)
+				.hp(500, 500, 1)
+				.attributes(&[Armored, Structure])
+				.building(3, 9.0),
+			UnitTypeId::FleetBeacon => protoss(300, 200, 0.0, 960.0)
+				.hp(500, 500, 1)
+				.attributes(&[Armored, Structure])
+				.building(3, 9.0),
+			// Zerg units
+			UnitTypeId::Larva => zerg(0, 0, 0.0, 0.0)
+				.hp(10, 0, 10)
+				.attributes(&[Light, Biological])
+				.unit(0.125, 0.79, 5.0, 0),
+			UnitTypeId::Drone => zerg(50, 0, 1.0, 272.0)
+				.hp(40, 0, 0)
+				.attributes(&[Light, Biological])
+				.unit(0.375, 3.94, 8.0, 1)
+				.weapon(ground(5, 1, 0.1, 1.07)),
+			UnitTypeId::Overlord => zerg(100, 0, 0.0, 400.0)
+				.hp(200, 0, 0)
+				.attributes(&[Armored, Biological])
+				.unit(1.0, 0.902, 11.0, 0)
+				.flying()
+				.provides(8.0),
+			UnitTypeId::Overseer => zerg(150, 50, 0.0, 272.0)
+				.hp(200, 0, 1)
+				.attributes(&[Armored, Biological])
+				.unit(1.0, 2.62, 11.0, 0)
+				.flying()
+				.provides(8.0),
+			UnitTypeId::Queen => zerg(150, 0, 2.0, 800.0)
+				.hp(175, 0, 1)
+				.attributes(&[Biological, Psionic])
+				.unit(0.875, 1.31, 9.0, 2)
+				.weapon(ground(4, 2, 5.0, 0.71))
+				.weapon(air(9, 1, 7.0, 0.71)),
+			UnitTypeId::Zergling => zerg(25, 0, 0.5, 384.0)
+				.hp(35, 0, 0)
+				.attributes(&[Light, Biological])
+				.unit(0.375, 4.13, 8.0, 1)
+				.weapon(ground(5, 1, 0.1, 0.5)),
+			UnitTypeId::Baneling => zerg(50, 25, 0.5, 320.0)
+				.hp(30, 0, 0)
+				.attributes(&[Biological])
+				.unit(0.375, 3.5, 8.0, 2)
+				.weapon(bonus(bonus(ground(16, 1, 0.25, 0.71), Light, 19), Structure, 64)),
+			UnitTypeId::Roach => zerg(75, 25, 2.0, 432.0)
+				.hp(145, 0, 1)
+				.attributes(&[Armored, Biological])
+				.unit(0.625, 3.15, 9.0, 2)
+				.weapon(ground(16, 1, 4.0, 1.43)),
+			UnitTypeId::Ravager => zerg(100, 100, 3.0, 192.0)
+				.hp(120, 0, 1)
+				.attributes(&[Biological])
+				.unit(0.75, 3.85, 9.0, 4)
+				.weapon(ground(16, 1, 6.0, 1.14)),
+			UnitTypeId::Hydralisk => zerg(100, 50, 2.0, 528.0)
+				.hp(90, 0, 0)
+				.attributes(&[Light, Biological])
+				.unit(0.625, 3.15, 9.0, 2)
+				.weapon(any(12, 1, 5.0, 0.59)),
+			UnitTypeId::LurkerMP => zerg(150, 150, 3.0, 400.0)
+				.hp(190, 0, 1)
+				.attributes(&[Armored, Biological])
+				.unit(0.75, 4.13, 11.0, 4),
+			UnitTypeId::LurkerMPBurrowed => zerg(150, 150, 3.0, 400.0)
+				.hp(190, 0, 1)
+				.attributes(&[Armored, Biological])
+				.unit(0.75, 0.0, 11.0, 4)
+				.weapon(bonus(ground(20, 1, 8.0, 1.43), Armored, 10)),
+			UnitTypeId::Infestor => zerg(100, 150, 2.0, 800.0)
+				.hp(90, 0, 0)
+				.attributes(&[Armored, Biological, Psionic])
+				.unit(0.75, 3.15, 10.0, 2),
+			UnitTypeId::SwarmHostMP => zerg(100, 75, 3.0, 640.0)
+				.hp(160, 0, 1)
+				.attributes(&[Armored, Biological])
+				.unit(0.75, 3.15, 11.0, 4),
+			UnitTypeId::LocustMP => zerg(0, 0, 0.0, 0.0)
+				.hp(50, 0, 0)
+				.attributes(&[Light, Biological, Summoned])
+				.unit(0.375, 2.62, 6.0, 0)
+				.weapon(ground(10, 1, 3.0, 0.43)),
+			UnitTypeId::Ultralisk => zerg(275, 200, 6.0, 880.0)
+				.hp(500, 0, 2)
+				.attributes(&[Armored, Biological, Massive])
+				.unit(1.0, 4.13, 9.0, 8)
+				.weapon(ground(35, 1, 1.0, 0.61)),
+			UnitTypeId::Mutalisk => zerg(100, 100, 2.0, 528.0)
+				.hp(120, 0, 0)
+				.attributes(&[Light, Biological])
+				.unit(0.5, 5.6, 11.0, 0)
+				.flying()
+				.weapon(any(9, 1, 3.0, 1.09)),
+			UnitTypeId::Corruptor => zerg(150, 100, 2.0, 640.0)
+				.hp(200, 0, 2)
+				.attributes(&[Armored, Biological])
+				.unit(0.625, 4.725, 10.0, 0)
+				.flying()
+				.weapon(bonus(air(14, 1, 6.0, 1.36), Massive, 6)),
+			UnitTypeId::BroodLord => zerg(300, 250, 4.0, 544.0)
+				.hp(225, 0, 1)
+				.attributes(&[Armored, Biological, Massive])
+				.unit(1.0, 1.97, 12.0, 0)
+				.flying()
+				.weapon(ground(20, 1, 10.0, 1.79)),
+			UnitTypeId::Broodling => zerg(0, 0, 0.0, 0.0)
+				.hp(20, 0, 0)
+				.attributes(&[Light, Biological, Summoned])
+				.unit(0.375, 5.37, 7.0, 0)
+				.weapon(ground(4, 1, 0.1, 0.46)),
+			UnitTypeId::Viper => zerg(100, 200, 3.0, 640.0)
+				.hp(150, 0, 1)
+				.attributes(&[Armored, Biological, Psionic])
+				.unit(0.75, 4.13, 11.0, 0)
+				.flying(),
+			// Zerg structures
+			UnitTypeId::Hatchery => zerg(350, 0, 0.0, 1600.0)
+				.hp(1500, 0, 1)
+				.attributes(&[Armored, Biological, Structure])
+				.building(5, 12.0)
+				.provides(6.0),
+			UnitTypeId::Lair => zerg(500, 100, 0.0, 1280.0)
+				.hp(2000, 0, 1)
+				.attributes(&[Armored, Biological, Structure])
+				.building(5, 12.0)
+				.provides(6.0),
+			UnitTypeId::Hive => zerg(700, 250, 0.0, 1600.0)
+				.hp(2500, 0, 1)
+				.attributes(&[Armored, Biological, Structure])
+				.building(5, 12.0)
+				.provides(6.0),
+			UnitTypeId::Extractor => zerg(75, 0, 0.0, 480.0)
+				.hp(500, 0, 1)
+				.attributes(&[Armored, Biological, Structure])
+				.building(3, 9.0),
+			UnitTypeId::SpawningPool => zerg(250, 0, 0.0, 1040.0)
+				.hp(1000, 0, 1)
+				.attributes(&[Armored, Biological, Structure])
+				.building(3, 9.0),
+			UnitTypeId::EvolutionChamber => zerg(125, 0, 0.0, 560.0)
+				.hp(750, 0, 1)
+				.attributes(&[Armored, Biological, Structure])
+				.building(3, 9.0),
+			UnitTypeId::RoachWarren => zerg(200, 0, 0.0, 880.0)
+				.hp(850, 0, 1)
+				.attributes(&[Armored, Biological, Structure])
+				.building(3, 9.0),
+			UnitTypeId::BanelingNest => zerg(150, 50, 0.0, 960.0)
+				.hp(850, 0, 1)
+				.attributes(&[Armored, Biological, Structure])
+				.building(3, 9.0),
+			UnitTypeId::SpineCrawler => zerg(150, 0, 0.0, 800.0)
+				.hp(300, 0, 2)
+				.attributes(&[Armored, Biological, Structure])
+				.building(2, 11.0)
+				.weapon(bonus(ground(25, 1, 7.0, 1.32), Armored, 5)),
+			UnitTypeId::SporeCrawler => zerg(125, 0, 0.0, 480.0)
+				.hp(400, 0, 1)
+				.attributes(&[Armored, Biological, Structure])
+				.building(2, 11.0)
+				.weapon(bonus(air(15, 1, 7.0, 0.61), Biological, 15)),
+			UnitTypeId::HydraliskDen => zerg(150, 100, 0.0, 640.0)
+				.hp(850, 0, 1)
+				.attributes(&[Armored, Biological, Structure])
+				.building(3, 9.0),
+			UnitTypeId::LurkerDenMP => zerg(150, 150, 0.0, 1280.0)
+				.hp(850, 0, 1)
+				.attributes(&[Armored, Biological, Structure])
+				.building(3, 9.0),
+			UnitTypeId::InfestationPit => zerg(150, 100, 0.0, 800.0)
+				.hp(850, 0, 1)
+				.attributes(&[Armored, Biological, Structure])
+				.building(3, 9.0),
+			UnitTypeId::Spire => zerg(250, 200, 0.0, 1600.0)
+				.hp(850, 0, 1)
+				.attributes(&[Armored, Biological, Structure])
+				.building(2, 9.0),
+			UnitTypeId::GreaterSpire => zerg(350, 350, 0.0, 1600.0)
+				.hp(1000, 0, 1)
+				.attributes(&[Armored, Biological, Structure])
+				.building(2, 9.0),
+			UnitTypeId::NydusNetwork => zerg(200, 150, 0.0, 800.0)
+				.hp(850, 0, 1)
+				.attributes(&[Armored, Biological, Structure])
+				.building(3, 9.0),
+			UnitTypeId::NydusCanal => zerg(75, 75, 0.0, 320.0)
+				.hp(300, 0, 1)
+				.attributes(&[Armored, Biological, Structure])
+				.building(3, 9.0),
+			UnitTypeId::UltraliskCavern => zerg(200, 200, 0.0, 1040.0)
+				.hp(850, 0, 1)
+				.attributes(&[Armored, Biological, Structure])
+				.building(3, 9.0),
+			UnitTypeId::CreepTumor => zerg(0, 0, 0.0, 240.0)
+				.hp(50, 0, 0)
+				.attributes(&[Armored, Structure])
+				.building(1, 10.0),
+		];
+
+		// Variants with the same stats
+		for (alias, original) in [
+			(UnitTypeId::SupplyDepotLowered, UnitTypeId::SupplyDepot),
+			(UnitTypeId::RefineryRich, UnitTypeId::Refinery),
+			(UnitTypeId::BarracksTechLab, UnitTypeId::TechLab),
+			(UnitTypeId::FactoryTechLab, UnitTypeId::TechLab),
+			(UnitTypeId::StarportTechLab, UnitTypeId::TechLab),
+			(UnitTypeId::BarracksReactor, UnitTypeId::Reactor),
+			(UnitTypeId::FactoryReactor, UnitTypeId::Reactor),
+			(UnitTypeId::StarportReactor, UnitTypeId::Reactor),
+			(UnitTypeId::AssimilatorRich, UnitTypeId::Assimilator),
+			(UnitTypeId::ExtractorRich, UnitTypeId::Extractor),
+			(UnitTypeId::OverseerSiegeMode, UnitTypeId::Overseer),
+			(UnitTypeId::CreepTumorBurrowed, UnitTypeId::CreepTumor),
+			(UnitTypeId::CreepTumorQueen, UnitTypeId::CreepTumor),
+		] {
+			let info = units[&original].clone();
+			units.insert(alias, info);
+		}
+		// Lifted buildings, which can't attack and move instead
+		for (flying, grounded) in [
+			(UnitTypeId::CommandCenterFlying, UnitTypeId::CommandCenter),
+			(UnitTypeId::OrbitalCommandFlying, UnitTypeId::OrbitalCommand),
+			(UnitTypeId::BarracksFlying, UnitTypeId::Barracks),
+			(UnitTypeId::FactoryFlying, UnitTypeId::Factory),
+			(UnitTypeId::StarportFlying, UnitTypeId::Starport),
+		] {
+			let mut info = units[&grounded].clone();
+			info.movement_speed = 0.94;
+			info.is_flying = true;
+			units.insert(flying, info);
+		}
+		// Burrowed units, which can't attack (except of those handled above) and move slower
+		for (burrowed, unburrowed) in [
+			(UnitTypeId::DroneBurrowed, UnitTypeId::Drone),
+			(UnitTypeId::ZerglingBurrowed, UnitTypeId::Zergling),
+			(UnitTypeId::BanelingBurrowed, UnitTypeId::Baneling),
+			(UnitTypeId::RoachBurrowed, UnitTypeId::Roach),
+			(UnitTypeId::RavagerBurrowed, UnitTypeId::Ravager),
+			(UnitTypeId::HydraliskBurrowed, UnitTypeId::Hydralisk),
+			(UnitTypeId::QueenBurrowed, UnitTypeId::Queen),
+			(UnitTypeId::InfestorBurrowed, UnitTypeId::Infestor),
+			(UnitTypeId::SwarmHostBurrowedMP, UnitTypeId::SwarmHostMP),
+			(UnitTypeId::UltraliskBurrowed, UnitTypeId::Ultralisk),
+		] {
+			let mut info = units[&unburrowed].clone();
+			if burrowed != UnitTypeId::BanelingBurrowed {
+				info.weapons.clear();
+			}
+			info.movement_speed = match burrowed {
+				UnitTypeId::RoachBurrowed => 2.0,
+				UnitTypeId::InfestorBurrowed => 2.0,
+				_ => 0.0,
+			};
+			units.insert(burrowed, info);
+		}
+		units
+	};
+	/// Static information about melee upgrades.
+	pub static ref UPGRADE_INFO: HashMap<UpgradeId, UpgradeInfo> = hashmap![
+		// Terran
+		UpgradeId::TerranInfantryWeaponsLevel1 => upgrade(100, 100, 2560.0),
+		UpgradeId::TerranInfantryWeaponsLevel2 => upgrade(175, 175, 3040.0),
+		UpgradeId::TerranInfantryWeaponsLevel3 => upgrade(250, 250, 3520.0),
+		UpgradeId::TerranInfantryArmorsLevel1 => upgrade(100, 100, 2560.0),
+		UpgradeId::TerranInfantryArmorsLevel2 => upgrade(175, 175, 3040.0),
+		UpgradeId::TerranInfantryArmorsLevel3 => upgrade(250, 250, 3520.0),
+		UpgradeId::TerranVehicleWeaponsLevel1 => upgrade(100, 100, 2560.0),
+		UpgradeId::TerranVehicleWeaponsLevel2 => upgrade(175, 175, 3040.0),
+		UpgradeId::TerranVehicleWeaponsLevel3 => upgrade(250, 250, 3520.0),
+		UpgradeId::TerranShipWeaponsLevel1 => upgrade(100, 100, 2560.0),
+		UpgradeId::TerranShipWeaponsLevel2 => upgrade(175, 175, 3040.0),
+		UpgradeId::TerranShipWeaponsLevel3 => upgrade(250, 250, 3520.0),
+		UpgradeId::TerranVehicleAndShipArmorsLevel1 => upgrade(100, 100, 2560.0),
+		UpgradeId::TerranVehicleAndShipArmorsLevel2 => upgrade(175, 175, 3040.0),
+		UpgradeId::TerranVehicleAndShipArmorsLevel3 => upgrade(250, 250, 3520.0),
+		UpgradeId::TerranBuildingArmor => upgrade(150, 150, 2240.0),
+		UpgradeId::HiSecAutoTracking => upgrade(100, 100, 1280.0),
+		UpgradeId::Stimpack => upgrade(100, 100, 2240.0),
+		UpgradeId::ShieldWall => upgrade(100, 100, 1760.0),
+		UpgradeId::PunisherGrenades => upgrade(50, 50, 960.0),
+		UpgradeId::PersonalCloaking => upgrade(150, 150, 1920.0),
+		UpgradeId::HighCapacityBarrels => upgrade(100, 100, 1760.0),
+		UpgradeId::DrillClaws => upgrade(75, 75, 1760.0),
+		UpgradeId::SmartServos => upgrade(100, 100, 1760.0),
+		UpgradeId::BansheeCloak => upgrade(100, 100, 1920.0),
+		UpgradeId::BansheeSpeed => upgrade(125, 125, 2720.0),
+		UpgradeId::BattlecruiserEnableSpecializations => upgrade(150, 150, 2240.0),
+		// Protoss
+		UpgradeId::ProtossGroundWeaponsLevel1 => upgrade(100, 100, 2880.0),
+		UpgradeId::ProtossGroundWeaponsLevel2 => upgrade(150, 150, 3440.0),
+		UpgradeId::ProtossGroundWeaponsLevel3 => upgrade(200, 200, 4000.0),
+		UpgradeId::ProtossGroundArmorsLevel1 => upgrade(100, 100, 2880.0),
+		UpgradeId::ProtossGroundArmorsLevel2 => upgrade(150, 150, 3440.0),
+		UpgradeId::ProtossGroundArmorsLevel3 => upgrade(200, 200, 4000.0),
+		UpgradeId::ProtossShieldsLevel1 => upgrade(150, 150, 2880.0),
+		UpgradeId::ProtossShieldsLevel2 => upgrade(225, 225, 3440.0),
+		UpgradeId::ProtossShieldsLevel3 => upgrade(300, 300, 4000.0),
+		UpgradeId::ProtossAirWeaponsLevel1 => upgrade(100, 100, 2880.0),
+		UpgradeId::ProtossAirWeaponsLevel2 => upgrade(175, 175, 3440.0),
+		UpgradeId::ProtossAirWeaponsLevel3 => upgrade(250, 250, 4000.0),
+		UpgradeId::ProtossAirArmorsLevel1 => upgrade(150, 150, 2880.0),
+		UpgradeId::ProtossAirArmorsLevel2 => upgrade(225, 225, 3440.0),
+		UpgradeId::ProtossAirArmorsLevel3 => upgrade(300, 300, 4000.0),
+		UpgradeId::WarpGateResearch => upgrade(50, 50, 2240.0),
+		UpgradeId::Charge => upgrade(100, 100, 2240.0),
+		UpgradeId::BlinkTech => upgrade(150, 150, 2720.0),
+		UpgradeId::AdeptPiercingAttack => upgrade(100, 100, 2240.0),
+		UpgradeId::PsiStormTech => upgrade(200, 200, 1760.0),
+		UpgradeId::DarkTemplarBlinkUpgrade => upgrade(100, 100, 2720.0),
+		UpgradeId::ExtendedThermalLance => upgrade(150, 150, 2240.0),
+		UpgradeId::GraviticDrive => upgrade(100, 100, 1280.0),
+		UpgradeId::ObserverGraviticBooster => upgrade(100, 100, 1280.0),
+		UpgradeId::PhoenixRangeUpgrade => upgrade(150, 150, 1440.0),
+		// Zerg
+		UpgradeId::ZergMeleeWeaponsLevel1 => upgrade(100, 100, 2560.0),
+		UpgradeId::ZergMeleeWeaponsLevel2 => upgrade(150, 150, 3040.0),
+		UpgradeId::ZergMeleeWeaponsLevel3 => upgrade(200, 200, 3520.0),
+		UpgradeId::ZergMissileWeaponsLevel1 => upgrade(100, 100, 2560.0),
+		UpgradeId::ZergMissileWeaponsLevel2 => upgrade(150, 150, 3040.0),
+		UpgradeId::ZergMissileWeaponsLevel3 => upgrade(200, 200, 3520.0),
+		UpgradeId::ZergGroundArmorsLevel1 => upgrade(150, 150, 2560.0),
+		UpgradeId::ZergGroundArmorsLevel2 => upgrade(225, 225, 3040.0),
+		UpgradeId::ZergGroundArmorsLevel3 => upgrade(300, 300, 3520.0),
+		UpgradeId::ZergFlyerWeaponsLevel1 => upgrade(100, 100, 2560.0),
+		UpgradeId::ZergFlyerWeaponsLevel2 => upgrade(175, 175, 3040.0),
+		UpgradeId::ZergFlyerWeaponsLevel3 => upgrade(250, 250, 3520.0),
+		UpgradeId::ZergFlyerArmorsLevel1 => upgrade(150, 150, 2560.0),
+		UpgradeId::ZergFlyerArmorsLevel2 => upgrade(225, 225, 3040.0),
+		UpgradeId::ZergFlyerArmorsLevel3 => upgrade(300, 300, 3520.0),
+		UpgradeId::Zerglingmovementspeed => upgrade(100, 100, 1760.0),
+		UpgradeId::Zerglingattackspeed => upgrade(200, 200, 2080.0),
+		UpgradeId::Overlordspeed => upgrade(100, 100, 960.0),
+		UpgradeId::Burrow => upgrade(100, 100, 1600.0),
+		UpgradeId::EvolveGroovedSpines => upgrade(100, 100, 1600.0),
+		UpgradeId::EvolveMuscularAugments => upgrade(100, 100, 1600.0),
+		UpgradeId::NeuralParasite => upgrade(150, 150, 1760.0),
+		UpgradeId::DiggingClaws => upgrade(150, 150, 1280.0),
+		UpgradeId::ChitinousPlating => upgrade(150, 150, 1760.0),
+		UpgradeId::AnabolicSynthesis => upgrade(150, 150, 960.0),
+	];
+	/// Static information about abilities, not including ones used to produce units and upgrades.
+	pub static ref ABILITY_INFO: HashMap<AbilityId, AbilityInfo> = {
+		let mut abilities = HashMap::<AbilityId, AbilityInfo>::new();
+		for (ability, range) in [
+			(AbilityId::EffectBlinkStalker, 8.0),
+			(AbilityId::EffectShadowStride, 8.0),
+			(AbilityId::FeedbackFeedback, 10.0),
+			(AbilityId::PsiStormPsiStorm, 9.0),
+			(AbilityId::ForceFieldForceField, 9.0),
+			(AbilityId::EffectPurificationNova, 13.0),
+			(AbilityId::OracleRevelationOracleRevelation, 9.0),
+			(AbilityId::EffectChronoBoostEnergyCost, 10.0),
+			(AbilityId::EMPEMP, 10.0),
+			(AbilityId::EffectGhostSnipe, 10.0),
+			(AbilityId::KD8ChargeKD8Charge, 5.0),
+			(AbilityId::YamatoYamatoGun, 10.0),
+			(AbilityId::EffectAntiArmorMissile, 10.0),
+			(AbilityId::EffectInterferenceMatrix, 9.0),
+			(AbilityId::BuildAutoTurretAutoTurret, 2.0),
+			(AbilityId::FungalGrowthFungalGrowth, 10.0),
+			(AbilityId::NeuralParasiteNeuralParasite, 8.0),
+			(AbilityId::EffectCorrosiveBile, 9.0),
+			(AbilityId::TransfusionTransfusion, 7.0),
+			(AbilityId::EffectAbduct, 9.0),
+			(AbilityId::BlindingCloudBlindingCloud, 11.0),
+			(AbilityId::ParasiticBombParasiticBomb, 8.0),
+		] {
+			abilities.entry(ability).or_default().cast_range = Some(range);
+		}
+		for (ability, cooldown) in ABILITY_COOLDOWNS.iter() {
+			abilities.entry(*ability).or_default().cooldown = Some(*cooldown);
+		}
+		for (ability, energy) in ABILITY_ENERGY_COSTS.iter() {
+			abilities.entry(*ability).or_default().energy_cost = Some(*energy);
+		}
+		abilities
+	};
+}
+
+/// Returns static information about given unit type.
+///
+/// Aliases without own entry (e.g. `AdeptPhaseShift`) fall back to information about original unit type.
+pub fn unit_info(unit: UnitTypeId) -> Option<&'static UnitInfo> {
+	UNIT_INFO
+		.get(&unit)
+		.or_else(|| UNIT_ALIAS.get(&unit).and_then(|alias| UNIT_INFO.get(alias)))
+}
+/// Returns static information about given upgrade.
+pub fn upgrade_info(upgrade: UpgradeId) -> Option<&'static UpgradeInfo> {
+	UPGRADE_INFO.get(&upgrade)
+}
+/// Returns static information about given ability.
+pub fn ability_info(ability: AbilityId) -> Option<&'static AbilityInfo> {
+	ABILITY_INFO.get(&ability)
+}
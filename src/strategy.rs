@@ -0,0 +1,441 @@
+//! Inference of opponent's opening from scouting information.
+//!
+//! Bot keeps [`StrategyInference`] in [`strategy`](crate::bot::Bot::strategy) field
+//! and updates it every step from what it has seen so far:
+//! enemy unit counts, enemy upgrades, enemy bases and proxy structures.
+//!
+//! Openings are recognized by [`Signature`]s, which are plain data,
+//! so you can easily extend or replace built-in ones:
+//! ```
+//! use rust_sc2::{prelude::*, strategy::*};
+//!
+//! #[bot]
+//! #[derive(Default)]
+//! struct MyBot;
+//! impl MyBot {
+//!     fn on_start(&mut self) {
+//!         self.strategy.add_signature(
+//!             Signature::new("Fast DTs", Opening::Tech)
+//!                 .race(Race::Protoss)
+//!                 .clue(Clue::seen(UnitTypeId::TwilightCouncil, 1, 240.0), 1.0)
+//!                 .clue(Clue::seen(UnitTypeId::DarkShrine, 1, 330.0), 2.0),
+//!         );
+//!     }
+//!     fn on_step(&mut self) {
+//!         if let Some(guess) = self.strategy.best() {
+//!             if guess.opening.is_proxy() && guess.confidence > 0.5 {
+//!                 /* Defend */
+//!             }
+//!         }
+//!     }
+//! }
+//! ```
+
+use crate::{
+	bot::{Bot, Locked},
+	distance::Distance,
+	ids::{UnitTypeId, UpgradeId},
+	player::Race,
+};
+use rustc_hash::FxHashMap;
+
+/// General kind of opponent's opening.
+#[variant_checkers]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Opening {
+	/// Production structures built near your base or somewhere hidden.
+	Proxy,
+	/// Early aggression with cheap units.
+	Rush,
+	/// Opponent takes natural expansion earlier than usual.
+	FastExpand,
+	/// Early investment into technology.
+	Tech,
+	/// Anything else, useful for custom signatures.
+	Other,
+}
+
+/// Single piece of scouting information used by [`Signature`].
+///
+/// All times are in-game seconds (like [`time`](Bot::time)).
+#[derive(Debug, Clone, PartialEq)]
+pub enum Clue {
+	/// At least `count` enemy units of given type were seen before `before` seconds.
+	/// Both ready and in-progress units are counted.
+	Seen {
+		/// Unit type to look for.
+		unit: UnitTypeId,
+		/// Minimal amount of units.
+		count: usize,
+		/// Latest time when units must be seen.
+		before: f32,
+	},
+	/// Enemy main base was scouted after `by` seconds, but no units of given type were seen until `by`.
+	NotSeen {
+		/// Unit type to look for.
+		unit: UnitTypeId,
+		/// Time until which unit must be absent.
+		by: f32,
+	},
+	/// Enemy got given upgrade before `before` seconds.
+	Upgrade {
+		/// Upgrade to look for.
+		upgrade: UpgradeId,
+		/// Latest time when upgrade must be detected.
+		before: f32,
+	},
+	/// Enemy had at least `count` bases before `before` seconds.
+	Bases {
+		/// Minimal amount of bases (including main).
+		count: usize,
+		/// Latest time when bases must be seen.
+		before: f32,
+	},
+	/// Enemy structure was seen closer to your start location than to opponent's one before `before` seconds.
+	/// Townhalls and creep tumors are not counted.
+	Proxy {
+		/// Latest time when proxy structure must be seen.
+		before: f32,
+	},
+}
+impl Clue {
+	/// Constructs [`Seen`](Self::Seen) clue.
+	pub fn seen(unit: UnitTypeId, count: usize, before: f32) -> Self {
+		Self::Seen { unit, count, before }
+	}
+	/// Constructs [`NotSeen`](Self::NotSeen) clue.
+	pub fn not_seen(unit: UnitTypeId, by: f32) -> Self {
+		Self::NotSeen { unit, by }
+	}
+	/// Constructs [`Upgrade`](Self::Upgrade) clue.
+	pub fn upgrade(upgrade: UpgradeId, before: f32) -> Self {
+		Self::Upgrade { upgrade, before }
+	}
+	/// Constructs [`Bases`](Self::Bases) clue.
+	pub fn bases(count: usize, before: f32) -> Self {
+		Self::Bases { count, before }
+	}
+	/// Constructs [`Proxy`](Self::Proxy) clue.
+	pub fn proxy(before: f32) -> Self {
+		Self::Proxy { before }
+	}
+}
+
+/// Description of an opening as a set of weighted [`Clue`]s.
+///
+/// Confidence of signature is a sum of weights of matched clues divided by total weight.
+#[derive(Debug, Clone)]
+pub struct Signature {
+	/// Name of the opening, e.g. `"12 Pool"`.
+	pub name: String,
+	/// Kind of the opening.
+	pub opening: Opening,
+	/// Race signature applies to, `None` means any race.
+	pub race: Option<Race>,
+	/// Clues with their weights.
+	pub clues: Vec<(Clue, f32)>,
+}
+impl Signature {
+	/// Constructs signature without any clues.
+	pub fn new<S: Into<String>>(name: S, opening: Opening) -> Self {
+		Self {
+			name: name.into(),
+			opening,
+			race: None,
+			clues: Vec::new(),
+		}
+	}
+	/// Restricts signature to given race.
+	pub fn race(mut self, race: Race) -> Self {
+		self.race = Some(race);
+		self
+	}
+	/// Adds clue with given weight.
+	pub fn clue(mut self, clue: Clue, weight: f32) -> Self {
+		self.clues.push((clue, weight));
+		self
+	}
+
+	/// Built-in signatures for common openings of all races.
+	pub fn defaults() -> Vec<Self> {
+		use UnitTypeId as U;
+
+		vec![
+			// Any race
+			Self::new("Proxy", Opening::Proxy).clue(Clue::proxy(240.0), 1.0),
+			Self::new("Fast Expand", Opening::FastExpand).clue(Clue::bases(2, 120.0), 1.0),
+			// Terran
+			Self::new("Proxy Barracks", Opening::Proxy)
+				.race(Race::Terran)
+				.clue(Clue::proxy(180.0), 2.0)
+				.clue(Clue::seen(U::Barracks, 2, 180.0), 1.0),
+			Self::new("Reaper Rush", Opening::Rush)
+				.race(Race::Terran)
+				.clue(Clue::seen(U::Reaper, 1, 150.0), 1.0)
+				.clue(Clue::seen(U::Reaper, 3, 240.0), 2.0),
+			Self::new("Marine Rush", Opening::Rush)
+				.race(Race::Terran)
+				.clue(Clue::seen(U::Barracks, 3, 180.0), 2.0)
+				.clue(Clue::not_seen(U::Refinery, 120.0), 1.0)
+				.clue(Clue::seen(U::Marine, 6, 210.0), 1.0),
+			Self::new("Banshee", Opening::Tech)
+				.race(Race::Terran)
+				.clue(Clue::seen(U::Starport, 1, 300.0), 1.0)
+				.clue(Clue::seen(U::Banshee, 1, 390.0), 2.0)
+				.clue(Clue::upgrade(UpgradeId::BansheeCloak, 480.0), 1.0),
+			Self::new("Tank Push", Opening::Tech)
+				.race(Race::Terran)
+				.clue(Clue::seen(U::Factory, 1, 240.0), 1.0)
+				.clue(Clue::seen(U::SiegeTank, 2, 360.0), 2.0),
+			// Zerg
+			Self::new("12 Pool", Opening::Rush)
+				.race(Race::Zerg)
+				.clue(Clue::seen(U::SpawningPool, 1, 75.0), 2.0)
+				.clue(Clue::seen(U::Zergling, 4, 130.0), 1.0),
+			Self::new("Hatch First", Opening::FastExpand)
+				.race(Race::Zerg)
+				.clue(Clue::bases(2, 90.0), 2.0)
+				.clue(Clue::not_seen(U::SpawningPool, 75.0), 1.0),
+			Self::new("Roach Rush", Opening::Rush)
+				.race(Race::Zerg)
+				.clue(Clue::seen(U::RoachWarren, 1, 180.0), 2.0)
+				.clue(Clue::seen(U::Roach, 4, 240.0), 1.0),
+			Self::new("Baneling Bust", Opening::Rush)
+				.race(Race::Zerg)
+				.clue(Clue::seen(U::BanelingNest, 1, 180.0), 2.0)
+				.clue(Clue::upgrade(UpgradeId::Zerglingmovementspeed, 180.0), 1.0),
+			Self::new("Mutalisk", Opening::Tech)
+				.race(Race::Zerg)
+				.clue(Clue::seen(U::Lair, 1, 270.0), 1.0)
+				.clue(Clue::seen(U::Spire, 1, 390.0), 2.0),
+			Self::new("Lurker", Opening::Tech)
+				.race(Race::Zerg)
+				.clue(Clue::seen(U::Lair, 1, 270.0), 1.0)
+				.clue(Clue::seen(U::LurkerDenMP, 1, 420.0), 2.0),
+			// Protoss
+			Self::new("Cannon Rush", Opening::Proxy)
+				.race(Race::Protoss)
+				.clue(Clue::proxy(180.0), 1.0)
+				.clue(Clue::seen(U::Forge, 1, 120.0), 1.0)
+				.clue(Clue::seen(U::PhotonCannon, 1, 180.0), 1.0),
+			Self::new("Proxy Gateways", Opening::Proxy)
+				.race(Race::Protoss)
+				.clue(Clue::proxy(180.0), 2.0)
+				.clue(Clue::seen(U::Gateway, 2, 180.0), 1.0),
+			Self::new("4 Gate", Opening::Rush)
+				.race(Race::Protoss)
+				.clue(Clue::seen(U::Gateway, 3, 240.0), 1.0)
+				.clue(Clue::upgrade(UpgradeId::WarpGateResearch, 300.0), 1.0)
+				.clue(Clue::seen(U::Gateway, 4, 300.0), 2.0),
+			Self::new("Stargate", Opening::Tech)
+				.race(Race::Protoss)
+				.clue(Clue::seen(U::Stargate, 1, 300.0), 2.0)
+				.clue(Clue::seen(U::Oracle, 1, 360.0), 1.0),
+			Self::new("Dark Templars", Opening::Tech)
+				.race(Race::Protoss)
+				.clue(Clue::seen(U::TwilightCouncil, 1, 270.0), 1.0)
+				.clue(Clue::seen(U::DarkShrine, 1, 360.0), 2.0),
+			Self::new("Robo", Opening::Tech)
+				.race(Race::Protoss)
+				.clue(Clue::seen(U::RoboticsFacility, 1, 300.0), 2.0)
+				.clue(Clue::seen(U::Immortal, 1, 390.0), 1.0),
+			Self::new("Blink Stalkers", Opening::Tech)
+				.race(Race::Protoss)
+				.clue(Clue::seen(U::TwilightCouncil, 1, 270.0), 1.0)
+				.clue(Clue::upgrade(UpgradeId::BlinkTech, 420.0), 2.0),
+		]
+	}
+}
+
+/// Guess about opponent's opening produced by [`StrategyInference`].
+#[derive(Debug, Clone)]
+pub struct Classification {
+	/// Name of matched signature.
+	pub name: String,
+	/// Kind of the opening.
+	pub opening: Opening,
+	/// Value in range `0..=1`, `1` means all clues of the signature matched.
+	pub confidence: f32,
+	/// Matched clues.
+	pub evidence: Vec<Clue>,
+}
+
+/// Scouting history and opening classifier.
+///
+/// Note: [`default`](Default::default) inference has no signatures,
+/// use [`new`](Self::new) to get one with [built-in signatures](Signature::defaults).
+#[derive(Debug, Clone, Default)]
+pub struct StrategyInference {
+	signatures: Vec<Signature>,
+	unit_times: FxHashMap<UnitTypeId, Vec<f32>>,
+	upgrade_times: FxHashMap<UpgradeId, f32>,
+	base_times: Vec<f32>,
+	proxy_time: Option<f32>,
+	main_scouted: Option<f32>,
+	classifications: Vec<Classification>,
+}
+impl StrategyInference {
+	/// Constructs inference with [built-in signatures](Signature::defaults).
+	pub fn new() -> Self {
+		Self {
+			signatures: Signature::defaults(),
+			..Default::default()
+		}
+	}
+	/// Adds custom signature.
+	pub fn add_signature(&mut self, signature: Signature) {
+		self.signatures.push(signature);
+	}
+	/// Removes all signatures, including built-in ones.
+	pub fn clear_signatures(&mut self) {
+		self.signatures.clear();
+	}
+	/// Returns all registered signatures.
+	pub fn signatures(&self) -> &[Signature] {
+		&self.signatures
+	}
+	/// Forgets everything seen, but keeps signatures.
+	pub fn reset(&mut self) {
+		self.unit_times.clear();
+		self.upgrade_times.clear();
+		self.base_times.clear();
+		self.proxy_time = None;
+		self.main_scouted = None;
+		self.classifications.clear();
+	}
+
+	/// All openings with non-zero confidence, sorted by confidence in descending order.
+	pub fn classifications(&self) -> &[Classification] {
+		&self.classifications
+	}
+	/// The most confident guess about opponent's opening.
+	pub fn best(&self) -> Option<&Classification> {
+		self.classifications.first()
+	}
+	/// Confidence of the most confident signature of given kind, `0` if nothing matched.
+	pub fn confidence(&self, opening: Opening) -> f32 {
+		self.classifications
+			.iter()
+			.find(|c| c.opening == opening)
+			.map_or(0.0, |c| c.confidence)
+	}
+
+	/// Time in seconds when `count` enemy units of given type were seen for the first time.
+	pub fn seen_at(&self, unit: UnitTypeId, count: usize) -> Option<f32> {
+		self.unit_times
+			.get(&unit)
+			.and_then(|times| times.get(count.checked_sub(1)?))
+			.copied()
+	}
+	/// Time in seconds when given enemy upgrade was detected.
+	pub fn upgrade_seen_at(&self, upgrade: UpgradeId) -> Option<f32> {
+		self.upgrade_times.get(&upgrade).copied()
+	}
+	/// Time in seconds when opponent had `count` bases for the first time.
+	pub fn bases_seen_at(&self, count: usize) -> Option<f32> {
+		self.base_times.get(count.checked_sub(1)?).copied()
+	}
+	/// Time in seconds when enemy proxy structure was seen for the first time.
+	pub fn proxy_seen_at(&self) -> Option<f32> {
+		self.proxy_time
+	}
+	/// Last time in seconds when enemy main base was visible.
+	pub fn main_scouted_at(&self) -> Option<f32> {
+		self.main_scouted
+	}
+
+	/// Checks if clue matches scouting history.
+	pub fn matches(&self, clue: &Clue) -> bool {
+		match *clue {
+			Clue::Seen { unit, count, before } => matches!(self.seen_at(unit, count), Some(t) if t <= before),
+			Clue::NotSeen { unit, by } => {
+				matches!(self.main_scouted, Some(t) if t >= by)
+					&& !matches!(self.seen_at(unit, 1), Some(t) if t <= by)
+			}
+			Clue::Upgrade { upgrade, before } => {
+				matches!(self.upgrade_seen_at(upgrade), Some(t) if t <= before)
+			}
+			Clue::Bases { count, before } => matches!(self.bases_seen_at(count), Some(t) if t <= before),
+			Clue::Proxy { before } => matches!(self.proxy_time, Some(t) if t <= before),
+		}
+	}
+
+	pub(crate) fn update(&mut self, bot: &Bot) {
+		let time = bot.time;
+
+		let counts = bot.enemies_current.iter().chain(&bot.enemies_ordered).fold(
+			FxHashMap::<UnitTypeId, usize>::default(),
+			|mut counts, (id, n)| {
+				*counts.entry(*id).or_default() += n;
+				counts
+			},
+		);
+		for (id, n) in counts {
+			let times = self.unit_times.entry(id).or_default();
+			while times.len() < n {
+				times.push(time);
+			}
+		}
+
+		for upgrade in bot.enemy_upgrades.read_lock().iter() {
+			self.upgrade_times.entry(*upgrade).or_insert(time);
+		}
+
+		let bases = bot.enemy_expansions().count();
+		while self.base_times.len() < bases {
+			self.base_times.push(time);
+		}
+
+		if self.proxy_time.is_none()
+			&& bot.units.enemy.structures.iter().any(|s| {
+				!(s.is_townhall()
+					|| matches!(
+						s.type_id(),
+						UnitTypeId::CreepTumor | UnitTypeId::CreepTumorBurrowed | UnitTypeId::CreepTumorQueen
+					)) && s.is_closer(s.distance(bot.enemy_start), bot.start_location)
+			}) {
+			self.proxy_time = Some(time);
+		}
+
+		if bot.is_visible(bot.enemy_start) {
+			self.main_scouted = Some(time);
+		}
+
+		self.classify(bot.enemy_race);
+	}
+
+	fn classify(&mut self, race: Race) {
+		let mut classifications = self
+			.signatures
+			.iter()
+			.filter(|s| race.is_random() || s.race.unwrap_or(race) == race)
+			.filter_map(|s| {
+				let total = s.clues.iter().map(|(_, w)| w).sum::<f32>();
+				if total <= 0.0 {
+					return None;
+				}
+
+				let mut matched = 0.0;
+				let mut evidence = Vec::new();
+				for (clue, weight) in &s.clues {
+					if self.matches(clue) {
+						matched += weight;
+						evidence.push(clue.clone());
+					}
+				}
+
+				if evidence.is_empty() {
+					return None;
+				}
+				Some(Classification {
+					name: s.name.clone(),
+					opening: s.opening,
+					confidence: matched / total,
+					evidence,
+				})
+			})
+			.collect::<Vec<_>>();
+		classifications.sort_unstable_by(|a, b| b.confidence.partial_cmp(&a.confidence).unwrap());
+		self.classifications = classifications;
+	}
+}
@@ -0,0 +1,283 @@
+//! Loading units into transports and dropping them.
+//!
+//! Drops are planned with [`Bot::plan_drop`] or [`Bot::plan_nydus_drop`],
+//! stored in [`transports`](crate::bot::Bot::transports) field and executed step by step
+//! with [`Bot::execute_drops`]. Passengers of own transports are tracked by tag.
+//! ```
+//! use rust_sc2::prelude::*;
+//!
+//! #[bot]
+//! #[derive(Default)]
+//! struct MyBot;
+//! impl MyBot {
+//!     fn on_step(&mut self) {
+//!         if self.transports.drops().is_empty() {
+//!             let marines = self.units.my.units.of_type(UnitTypeId::Marine);
+//!             let target = self.enemy_start;
+//!             self.plan_drop(&marines, target);
+//!         }
+//!         self.pick_up_endangered(0.3);
+//!         self.execute_drops();
+//!     }
+//! }
+//! ```
+
+use crate::{
+	action::Target,
+	bot::Bot,
+	distance::*,
+	geometry::Point2,
+	ids::{AbilityId, UnitTypeId},
+	units::Units,
+};
+use rustc_hash::FxHashMap;
+use std::{cmp::Reverse, mem};
+
+/// Transports which can carry units along air path.
+const AIR_TRANSPORTS: [UnitTypeId; 3] = [UnitTypeId::Medivac, UnitTypeId::WarpPrism, UnitTypeId::Overlord];
+
+/// Stage of the drop.
+#[variant_checkers]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum DropState {
+	/// Units are boarding the transport.
+	Loading,
+	/// Transport is moving to the target and will unload there.
+	Unloading,
+	/// All units are unloaded.
+	Done,
+	/// Transport died.
+	Failed,
+}
+
+/// Drop of units with a single transport.
+#[derive(Debug, Clone)]
+pub struct DropPlan {
+	/// Tag of the transport (nydus network for nydus drops).
+	pub transport: u64,
+	/// Tag of nydus worm, where units are unloaded, `None` for air transports.
+	pub exit: Option<u64>,
+	/// Tags of units which should be dropped.
+	pub cargo: Vec<u64>,
+	/// Position where units are unloaded.
+	pub target: Point2,
+	/// Current stage of the drop.
+	pub state: DropState,
+}
+
+/// All planned and ongoing drops and passengers of own transports.
+#[derive(Debug, Default, Clone)]
+pub struct Transports {
+	drops: Vec<DropPlan>,
+	passengers: FxHashMap<u64, u64>,
+}
+impl Transports {
+	/// All drops, including completed and failed on last execution.
+	pub fn drops(&self) -> &[DropPlan] {
+		&self.drops
+	}
+	/// Returns unfinished drop of given transport.
+	pub fn drop_of(&self, transport: u64) -> Option<&DropPlan> {
+		self.drops
+			.iter()
+			.find(|d| d.transport == transport && (d.state.is_loading() || d.state.is_unloading()))
+	}
+	/// Checks if unit with given tag is a transport or cargo of unfinished drop.
+	pub fn is_busy(&self, tag: u64) -> bool {
+		self.drops.iter().any(|d| {
+			(d.state.is_loading() || d.state.is_unloading()) && (d.transport == tag || d.cargo.contains(&tag))
+		})
+	}
+	/// Cancels drop of given transport. Units stay where they are.
+	pub fn cancel(&mut self, transport: u64) {
+		self.drops.retain(|d| d.transport != transport);
+	}
+	/// Returns tag of transport (or bunker) unit with given tag is inside.
+	pub fn transport_of(&self, passenger: u64) -> Option<u64> {
+		self.passengers.get(&passenger).copied()
+	}
+	/// Passengers of own transports: (passenger tag, transport tag).
+	pub fn passengers(&self) -> &FxHashMap<u64, u64> {
+		&self.passengers
+	}
+
+	pub(crate) fn update(&mut self, units: &Units) {
+		self.passengers = units
+			.iter()
+			.flat_map(|u| u.passengers().iter().map(move |p| (p.tag, u.tag())))
+			.collect();
+	}
+}
+
+impl Bot {
+	/// Returns own air transports, which are free of drops and can carry units now.
+	pub fn free_transports(&self) -> Units {
+		self.units.my.units.filter(|u| {
+			AIR_TRANSPORTS.contains(&u.type_id())
+				&& u.cargo_space_max().unwrap_or(0) > 0
+				&& !self.transports.is_busy(u.tag())
+		})
+	}
+
+	/// Plans drop of given units at given position, distributing them
+	/// among free transports by cargo size (the biggest units first).
+	///
+	/// Units which don't fit or can't be loaded (e.g. flying) are skipped.
+	/// Returns tags of transports used for the drop.
+	pub fn plan_drop(&mut self, units: &Units, target: Point2) -> Vec<u64> {
+		let mut transports = self
+			.free_transports()
+			.iter()
+			.filter_map(|t| Some((t.tag(), t.cargo_left()?, Vec::new())))
+			.collect::<Vec<(u64, u32, Vec<u64>)>>();
+
+		let mut cargo = units
+			.iter()
+			.filter(|u| !u.is_flying() && u.cargo_size() > 0 && !self.transports.is_busy(u.tag()))
+			.collect::<Vec<_>>();
+		cargo.sort_by_key(|u| Reverse(u.cargo_size()));
+
+		for u in cargo {
+			let size = u.cargo_size();
+			if let Some((_, left, tags)) = transports.iter_mut().find(|(_, left, _)| *left >= size) {
+				*left -= size;
+				tags.push(u.tag());
+			}
+		}
+
+		transports
+			.into_iter()
+			.filter(|(_, _, cargo)| !cargo.is_empty())
+			.map(|(transport, _, cargo)| {
+				self.transports.drops.push(DropPlan {
+					transport,
+					exit: None,
+					cargo,
+					target,
+					state: DropState::Loading,
+				});
+				transport
+			})
+			.collect()
+	}
+	/// Plans drop of given units through nydus network, unloading them from given nydus worm.
+	///
+	/// Returns `false` if network or worm doesn't exist.
+	pub fn plan_nydus_drop(&mut self, units: &Units, network: u64, worm: u64) -> bool {
+		let structures = &self.units.my.structures;
+		let target = match (structures.get(network), structures.get(worm)) {
+			(Some(_), Some(worm)) => worm.position(),
+			_ => return false,
+		};
+		let cargo = units
+			.iter()
+			.filter(|u| !u.is_flying() && u.cargo_size() > 0 && !self.transports.is_busy(u.tag()))
+			.map(|u| u.tag())
+			.collect();
+		self.transports.drops.push(DropPlan {
+			transport: network,
+			exit: Some(worm),
+			cargo,
+			target,
+			state: DropState::Loading,
+		});
+		true
+	}
+
+	/// Gives orders to transports and units taking part in drops and updates states of drops.
+	///
+	/// Air transports fly to the target along a path avoiding known threats.
+	/// Completed and failed drops are kept until next execution, so their results can be checked.
+	pub fn execute_drops(&mut self) {
+		let mut drops = mem::take(&mut self.transports.drops);
+		drops.retain(|d| d.state.is_loading() || d.state.is_unloading());
+
+		for plan in &mut drops {
+			let exit_alive = !matches!(plan.exit, Some(exit) if !self.units.my.structures.contains_tag(exit));
+			let transport = match self.units.my.all.get(plan.transport) {
+				Some(transport) if exit_alive => transport,
+				_ => {
+					plan.state = DropState::Failed;
+					continue;
+				}
+			};
+
+			let transport_tag = plan.transport;
+			let inside = |tag: &u64| self.transports.transport_of(*tag) == Some(transport_tag);
+			// Units which aren't visible and aren't inside are considered dead
+			plan.cargo
+				.retain(|tag| inside(tag) || self.units.my.units.contains_tag(*tag));
+
+			match plan.state {
+				DropState::Loading => {
+					let waiting = plan.cargo.iter().filter(|tag| !inside(tag)).collect::<Vec<_>>();
+					if !waiting.is_empty() {
+						for tag in waiting {
+							if let Some(u) = self.units.my.units.get(*tag) {
+								if !u.is_using(AbilityId::Smart) {
+									u.smart(Target::Tag(transport_tag), false);
+								}
+							}
+						}
+						continue;
+					}
+
+					plan.state = DropState::Unloading;
+					match plan.exit.and_then(|exit| self.units.my.structures.get(exit)) {
+						Some(exit) => exit.unload_all(false),
+						None => {
+							let threats = self.threats_to(transport);
+							let route = self.detours(transport, transport.position(), plan.target, &threats);
+							for (i, pos) in route.into_iter().enumerate() {
+								transport.move_to(Target::Pos(pos), i > 0);
+							}
+							transport.unload_all_at(plan.target, true);
+						}
+					}
+				}
+				DropState::Unloading => {
+					if !plan.cargo.iter().any(inside) {
+						plan.state = DropState::Done;
+					} else if transport.is_idle() || plan.exit.is_some() {
+						match plan.exit.and_then(|exit| self.units.my.structures.get(exit)) {
+							Some(exit) => exit.unload_all(false),
+							None => transport.unload_all_at(plan.target, false),
+						}
+					}
+				}
+				_ => {}
+			}
+		}
+
+		self.transports.drops = drops;
+	}
+
+	/// Orders free air transports to pick up nearby attacked units
+	/// with health percentage below given value to save them from damage.
+	///
+	/// Picked units stay inside until they're unloaded manually or with a drop.
+	pub fn pick_up_endangered(&self, health: f32) {
+		let mut transports = self
+			.free_transports()
+			.iter()
+			.filter_map(|t| Some((t.clone(), t.cargo_left()?)))
+			.collect::<Vec<_>>();
+
+		for u in self.units.my.units.iter().filter(|u| {
+			!u.is_flying()
+				&& u.cargo_size() > 0
+				&& u.is_attacked()
+				&& matches!(u.hits_percentage(), Some(hp) if hp < health)
+		}) {
+			let size = u.cargo_size();
+			if let Some((transport, left)) = transports
+				.iter_mut()
+				.filter(|(t, left)| *left >= size && t.is_closer(5.0, u))
+				.min_by(|(a, _), (b, _)| a.distance_squared(u).partial_cmp(&b.distance_squared(u)).unwrap())
+			{
+				*left -= size;
+				transport.load(u.tag(), false);
+			}
+		}
+	}
+}
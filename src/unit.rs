@@ -6,27 +6,32 @@ use crate::{
 	bot::{LockBool, LockOwned, LockU32, Locked, Reader, Rl, Rs, Rw},
 	consts::{
 		RaceValues, ANTI_ARMOR_BUFF, DAMAGE_BONUS_PER_UPGRADE, FRAMES_PER_SECOND, MISSED_WEAPONS,
-		OFF_CREEP_SPEED_UPGRADES, SPEED_BUFFS, SPEED_ON_CREEP, SPEED_UPGRADES, WARPGATE_ABILITIES,
+		OFF_CREEP_SPEED_UPGRADES, RACE_VALUES, SPEED_BUFFS, SPEED_ON_CREEP, SPEED_UPGRADES,
+		WARPGATE_ABILITIES,
 	},
 	distance::Distance,
 	game_data::{Attribute, Cost, GameData, TargetType, UnitTypeData, Weapon},
 	game_state::Alliance,
 	geometry::{Point2, Point3},
-	ids::{AbilityId, BuffId, UnitTypeId, UpgradeId},
+	ids::{AbilityId, BuffId, ParseId, UnitTypeId, UpgradeId},
 	pixel_map::{PixelMap, VisibilityMap},
 	player::Race,
+	static_data::{unit_info, UnitInfo},
 	units::Container,
 	utils::CacheMap,
 	FromProto,
 };
 use lazy_init::Lazy as LazyInit;
-use num_traits::FromPrimitive;
+use num_traits::ToPrimitive;
 use once_cell::sync::Lazy;
 use rustc_hash::{FxHashMap, FxHashSet};
 use sc2_proto::raw::{
 	CloakState as ProtoCloakState, DisplayType as ProtoDisplayType, Unit as ProtoUnit,
 	UnitOrder_oneof_target as ProtoTarget,
 };
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
 
 #[derive(Default, Clone)]
 pub(crate) struct DataForUnit {
@@ -44,13 +49,25 @@ pub(crate) struct DataForUnit {
 	pub game_step: Rs<LockU32>,
 	pub game_loop: Rs<LockU32>,
 	pub available_frames: Rw<FxHashMap<u64, u32>>,
+	pub ability_cooldowns: Rw<FxHashMap<u64, FxHashMap<AbilityId, AbilityCooldown>>>,
 }
 
+/// Cooldown of ability used by unit, tracked locally.
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct AbilityCooldown {
+	/// Game loop when ability was ordered.
+	pub ordered: u32,
+	/// Game loop when ability will be ready again.
+	pub ready: u32,
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub(crate) struct UnitBase {
 	pub display_type: Rl<DisplayType>,
 	pub alliance: Alliance,
 	pub tag: u64,
 	pub type_id: Rl<UnitTypeId>,
+	pub raw_type_id: u32,
 	pub owner: u32,
 	pub position: Point2,
 	pub position3d: Point3,
@@ -95,7 +112,9 @@ pub(crate) struct UnitBase {
 	pub rally_targets: Vec<RallyTarget>,
 
 	// cache
+	#[cfg_attr(feature = "serde", serde(skip))]
 	real_speed: LazyInit<f32>,
+	#[cfg_attr(feature = "serde", serde(skip))]
 	real_weapon_vs: Lazy<CacheMap<u64, (f32, f32)>>,
 }
 
@@ -111,12 +130,43 @@ pub(crate) type SharedUnitData = Rs<DataForUnit>;
 
 /// Unit structure contains some raw data, helper methods for it's analysis
 /// and some methods for actions execution.
+///
+/// With `serde` feature only raw data of unit is serialized.
+/// Deserialized unit isn't linked to any bot, so helpers which depend on game data
+/// fall back to [static data](crate::static_data).
+/// Use [`Bot::from_snapshot`](crate::bot::Bot::from_snapshot) to get units linked to the bot.
 #[derive(Clone)]
 pub struct Unit {
 	data: SharedUnitData,
 	pub(crate) base: Rs<UnitBase>,
 }
 
+impl Unit {
+	/// Returns the same unit linked to the given data.
+	pub(crate) fn with_data(&self, data: &SharedUnitData) -> Self {
+		Self {
+			data: Rs::clone(data),
+			base: Rs::clone(&self.base),
+		}
+	}
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Unit {
+	fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		self.base.serialize(serializer)
+	}
+}
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Unit {
+	fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		Ok(Self {
+			data: Default::default(),
+			base: Rs::new(UnitBase::deserialize(deserializer)?),
+		})
+	}
+}
+
 impl Unit {
 	/////////////////////////////////////////////////
 	// Fields are populated based on type/alliance //
@@ -148,6 +198,14 @@ impl Unit {
 	pub fn type_id(&self) -> UnitTypeId {
 		*self.base.type_id.read_lock()
 	}
+	/// Raw id of unit's type received from the game.
+	///
+	/// Types unknown to the library (e.g. from mods or newer game versions)
+	/// have [`type_id`](Self::type_id) `NotAUnit`, but their raw id is kept here.
+	#[inline]
+	pub fn raw_type_id(&self) -> u32 {
+		self.base.raw_type_id
+	}
 	/// Player id of the owner. Normally it should match your [`player_id`] for owned units
 	/// and [`enemy_player_id`] for opponent's units.
 	///
@@ -419,6 +477,17 @@ impl Unit {
 	fn type_data(&self) -> Option<&UnitTypeData> {
 		self.data.game_data.units.get(&self.type_id())
 	}
+	/// Takes value from game data or from [static data](crate::static_data) if game data lacks the entry.
+	fn type_value<'a, T>(
+		&'a self,
+		data: impl FnOnce(&'a UnitTypeData) -> T,
+		info: impl FnOnce(&'static UnitInfo) -> T,
+	) -> Option<T> {
+		match self.type_data() {
+			Some(type_data) => Some(data(type_data)),
+			None => unit_info(self.type_id()).map(info),
+		}
+	}
 	fn upgrades(&self) -> Reader<FxHashSet<UpgradeId>> {
 		if self.is_mine() {
 			self.data.upgrades.read_lock()
@@ -528,7 +597,8 @@ impl Unit {
 	}
 	/// Race of unit, dependent on it's type.
 	pub fn race(&self) -> Race {
-		self.type_data().map_or(Race::Random, |data| data.race)
+		self.type_value(|data| data.race, |info| info.race)
+			.unwrap_or(Race::Random)
 	}
 	/// There're some units inside transport or bunker.
 	pub fn has_cargo(&self) -> bool {
@@ -556,24 +626,29 @@ impl Unit {
 		if self.is_addon() {
 			Some(2)
 		} else {
-			self.footprint_radius().map(|radius| (radius * 2.0) as usize)
+			self.footprint_radius()
+				.map(|radius| (radius * 2.0) as usize)
+				.or_else(|| unit_info(self.type_id()).and_then(|info| info.building_size))
 		}
 	}
 	/// How long a unit takes to build.
 	pub fn build_time(&self) -> f32 {
-		self.type_data().map_or(0.0, |data| data.build_time)
+		self.type_value(|data| data.build_time, |info| info.build_time)
+			.unwrap_or(0.0)
 	}
 	/// Space that unit takes in transports and bunkers.
 	pub fn cargo_size(&self) -> u32 {
-		self.type_data().map_or(0, |data| data.cargo_size)
+		self.type_value(|data| data.cargo_size, |info| info.cargo_size)
+			.unwrap_or(0)
 	}
 	/// How far unit can see.
 	pub fn sight_range(&self) -> f32 {
-		self.type_data().map_or(0.0, |data| data.sight_range)
+		self.type_value(|data| data.sight_range, |info| info.sight_range)
+			.unwrap_or(0.0)
 	}
 	/// Initial armor of unit without considering upgrades and buffs.
 	pub fn armor(&self) -> i32 {
-		self.type_data().map_or(0, |data| data.armor)
+		self.type_value(|data| data.armor, |info| info.armor).unwrap_or(0)
 	}
 	/// Returns point with given offset towards unit face direction.
 	pub fn towards_facing(&self, offset: f32) -> Point2 {
@@ -626,11 +701,13 @@ impl Unit {
 
 	/// Returns how much supply this unit uses.
 	pub fn supply_cost(&self) -> f32 {
-		self.type_data().map_or(0.0, |data| data.food_required)
+		self.type_value(|data| data.food_required, |info| info.food_required)
+			.unwrap_or(0.0)
 	}
 	/// Returns cost of unit.
 	pub fn cost(&self) -> Cost {
-		self.type_data().map_or(Cost::default(), |data| data.cost())
+		self.type_value(|data| data.cost(), |info| info.cost())
+			.unwrap_or_default()
 	}
 	/// Returns health percentage (current health divided by max health).
 	/// Value in range from `0` to `1`.
@@ -700,7 +777,8 @@ impl Unit {
 	///
 	/// Use [`real_speed`](Self::real_speed) to get speed including buffs and upgrades.
 	pub fn speed(&self) -> f32 {
-		self.type_data().map_or(0.0, |data| data.movement_speed)
+		self.type_value(|data| data.movement_speed, |info| info.movement_speed)
+			.unwrap_or(0.0)
 	}
 	/// Returns actual speed of the unit calculated including buffs and upgrades.
 	pub fn real_speed(&self) -> f32 {
@@ -734,7 +812,13 @@ impl Unit {
 
 			// ---- Creep ----
 			// On creep
-			if self.data.creep.read_lock()[self.position()].is_set() {
+			if self
+				.data
+				.creep
+				.read_lock()
+				.get(<(usize, usize)>::from(self.position()))
+				.is_some_and(|p| p.is_set())
+			{
 				if let Some(increase) = SPEED_ON_CREEP.get(&unit_type) {
 					speed *= increase;
 				}
@@ -761,12 +845,15 @@ impl Unit {
 	}
 	/// Attributes of unit, dependent on it's type.
 	pub fn attributes(&self) -> &[Attribute] {
-		self.type_data().map_or(&[], |data| data.attributes.as_slice())
+		self.type_value(
+			|data| data.attributes.as_slice(),
+			|info| info.attributes.as_slice(),
+		)
+		.unwrap_or_default()
 	}
 	/// Checks if unit has given attribute.
 	pub fn has_attribute(&self, attribute: Attribute) -> bool {
-		self.type_data()
-			.map_or(false, |data| data.attributes.contains(&attribute))
+		self.attributes().contains(&attribute)
 	}
 	/// Checks if unit has `Light` attribute.
 	pub fn is_light(&self) -> bool {
@@ -863,8 +950,7 @@ impl Unit {
 				.get(&UnitTypeId::Ravager)
 				.map_or(&[], |data| data.weapons.as_slice()),
 			unit_type => self
-				.type_data()
-				.map(|data| data.weapons.as_slice())
+				.type_value(|data| data.weapons.as_slice(), |info| info.weapons.as_slice())
 				.filter(|weapons| !weapons.is_empty())
 				.or_else(|| MISSED_WEAPONS.get(&unit_type).map(|ws| ws.as_slice()))
 				.unwrap_or_default(),
@@ -1764,6 +1850,37 @@ impl Unit {
 			.insert(self.tag(), self.data.game_loop.get_locked() + duration);
 	}
 
+	/// Returns amount of game loops left until given ability is ready to use again.
+	///
+	/// Cooldown is tracked locally from your commands and [`cooldown`] values,
+	/// and validated against available abilities each step.
+	///
+	/// [`cooldown`]: crate::game_data::AbilityData::cooldown
+	pub fn ability_cooldown(&self, ability: AbilityId) -> u32 {
+		let game_loop = self.data.game_loop.get_locked();
+		self.data
+			.ability_cooldowns
+			.read_lock()
+			.get(&self.tag())
+			.and_then(|cooldowns| cooldowns.get(&ability))
+			.map_or(0, |cooldown| cooldown.ready.saturating_sub(game_loop))
+	}
+	/// Checks if unit can use given ability right now.
+	///
+	/// Unlike [`has_ability`](Self::has_ability) it also considers commands given on current step:
+	/// ability shouldn't be on locally tracked [cooldown](Self::ability_cooldown)
+	/// and unit should have enough energy for it.
+	pub fn can_cast(&self, ability: AbilityId) -> bool {
+		let data = self.data.game_data.abilities.get(&ability);
+		let remap = data.and_then(|data| data.remaps_to_ability_id);
+
+		let energy_cost = data.and_then(|data| data.energy_cost).unwrap_or(0);
+
+		(self.has_ability(ability) || matches!(remap, Some(remap) if self.has_ability(remap)))
+			&& self.ability_cooldown(ability) == 0
+			&& (energy_cost == 0 || matches!(self.energy(), Some(energy) if energy >= energy_cost))
+	}
+
 	// Actions
 
 	/// Toggles autocast on given ability.
@@ -1785,6 +1902,25 @@ impl Unit {
 			.entry((ability, target, queue))
 			.or_default()
 			.push(self.tag());
+
+		if queue {
+			return;
+		}
+		if let Some(data) = self.data.game_data.abilities.get(&ability) {
+			if let Some(cooldown) = data.cooldown {
+				let ordered = self.data.game_loop.get_locked();
+				let cooldown = AbilityCooldown {
+					ordered,
+					ready: ordered + cooldown,
+				};
+				let mut cooldowns = self.data.ability_cooldowns.write_lock();
+				let cooldowns = cooldowns.entry(self.tag()).or_default();
+				cooldowns.insert(ability, cooldown);
+				if let Some(remap) = data.remaps_to_ability_id {
+					cooldowns.insert(remap, cooldown);
+				}
+			}
+		}
 	}
 	/// Orders unit to use given ability (This is equivalent of `unit.command(ability, Target::None, queue)`).
 	pub fn use_ability(&self, ability: AbilityId, queue: bool) {
@@ -1844,13 +1980,24 @@ impl Unit {
 	}
 	/// Orders worker to build race gas building on given geyser.
 	pub fn build_gas(&self, target: u64, queue: bool) {
-		self.command(
-			self.data.game_data.units[&self.data.race_values.gas]
-				.ability
-				.unwrap(),
-			Target::Tag(target),
-			queue,
-		)
+		let gas = match self.data.race_values.gas {
+			UnitTypeId::NotAUnit => RACE_VALUES
+				.get(&self.race())
+				.map_or(UnitTypeId::NotAUnit, |v| v.gas),
+			gas => gas,
+		};
+		let ability = match self.data.game_data.units.get(&gas) {
+			Some(type_data) => type_data.ability,
+			None => match gas {
+				UnitTypeId::Refinery => Some(AbilityId::TerranBuildRefinery),
+				UnitTypeId::Assimilator => Some(AbilityId::ProtossBuildAssimilator),
+				UnitTypeId::Extractor => Some(AbilityId::ZergBuildExtractor),
+				_ => None,
+			},
+		};
+		if let Some(ability) = ability {
+			self.command(ability, Target::Tag(target), queue);
+		}
 	}
 	/// Orders worker to build something on given position.
 	pub fn build(&self, unit: UnitTypeId, target: Point2, queue: bool) {
@@ -1901,6 +2048,18 @@ impl Unit {
 	pub fn land(&self, target: Point2, queue: bool) {
 		self.command(AbilityId::Land, Target::Pos(target), queue);
 	}
+	/// Orders transport, bunker or nydus to load unit with given tag.
+	pub fn load(&self, unit: u64, queue: bool) {
+		self.command(AbilityId::Load, Target::Tag(unit), queue);
+	}
+	/// Orders flying transport to unload all passengers at given position.
+	pub fn unload_all_at(&self, target: Point2, queue: bool) {
+		self.command(AbilityId::UnloadAllAt, Target::Pos(target), queue);
+	}
+	/// Orders bunker, nydus or command center to unload all passengers in place.
+	pub fn unload_all(&self, queue: bool) {
+		self.command(AbilityId::UnloadAll, Target::None, queue);
+	}
 }
 
 impl From<&Unit> for Point2 {
@@ -1920,10 +2079,8 @@ impl Unit {
 	pub(crate) fn from_proto(data: SharedUnitData, visibility: &VisibilityMap, u: &ProtoUnit) -> Self {
 		let pos = u.get_pos();
 		let position = Point2::from_proto(pos);
-		let type_id = {
-			let id = u.get_unit_type();
-			UnitTypeId::from_u32(id).unwrap_or_else(|| panic!("There's no `UnitTypeId` with value {}", id))
-		};
+		let raw_type_id = u.get_unit_type();
+		let type_id = UnitTypeId::parse_id(raw_type_id).unwrap_or(UnitTypeId::NotAUnit);
 		let is_burrowed = u.get_is_burrowed();
 		let (is_cloaked, is_revealed) = if is_burrowed {
 			(true, false)
@@ -1953,6 +2110,7 @@ impl Unit {
 				alliance: Alliance::from_proto(u.get_alliance()),
 				tag: u.get_tag(),
 				type_id: Rl::new(type_id),
+				raw_type_id,
 				owner: u.get_owner() as u32,
 				position,
 				position3d: Point3::from_proto(pos),
@@ -1964,9 +2122,7 @@ impl Unit {
 				buffs: u
 					.get_buff_ids()
 					.iter()
-					.map(|b| {
-						BuffId::from_u32(*b).unwrap_or_else(|| panic!("There's no `BuffId` with value {}", b))
-					})
+					.filter_map(|b| BuffId::parse_id(*b))
 					.collect(),
 				detect_range: match type_id {
 					UnitTypeId::Observer => 11.0,
@@ -1999,11 +2155,9 @@ impl Unit {
 					.get_orders()
 					.iter()
 					.map(|order| UnitOrder {
-						ability: {
-							let id = order.get_ability_id();
-							AbilityId::from_u32(id)
-								.unwrap_or_else(|| panic!("There's no `AbilityId` with value {}", id))
-						},
+						// Order is kept with unknown ability, so unit isn't considered idle
+						ability: AbilityId::parse_id(order.get_ability_id()).unwrap_or(AbilityId::NullNull),
+						raw_ability: order.get_ability_id(),
 						target: match &order.target {
 							Some(ProtoTarget::target_world_space_pos(pos)) => {
 								Target::Pos(Point2::from_proto(pos))
@@ -2026,11 +2180,8 @@ impl Unit {
 						shield_max: p.get_shield_max(),
 						energy: p.get_energy(),
 						energy_max: p.get_energy_max(),
-						type_id: {
-							let id = p.get_unit_type();
-							UnitTypeId::from_u32(id)
-								.unwrap_or_else(|| panic!("There's no `UnitTypeId` with value {}", id))
-						},
+						type_id: UnitTypeId::parse_id(p.get_unit_type()).unwrap_or(UnitTypeId::NotAUnit),
+						raw_type_id: p.get_unit_type(),
 					})
 					.collect(),
 				cargo_space_taken: u.cargo_space_taken.map(|x| x as u32),
@@ -2058,10 +2209,305 @@ impl Unit {
 	}
 }
 
+/// First tag given to built units, which don't have tag set explicitly.
+pub const BUILDER_TAGS_START: u64 = 1 << 48;
+static NEXT_TAG: AtomicU64 = AtomicU64::new(BUILDER_TAGS_START);
+
+impl Unit {
+	/// Starts building unit of given type without running the game, mainly for tests.
+	///
+	/// Built unit isn't linked to any bot, so helpers which depend on game data
+	/// fall back to [static data](crate::static_data).
+	/// To get units linked to the bot, pass them to
+	/// [`Bot::from_snapshot`](crate::bot::Bot::from_snapshot).
+	///
+	/// Health, shield, radius and whether unit is flying are taken from static data,
+	/// [`flying`](UnitBuilder::flying) can be used for unit types missing there.
+	/// Tags not set explicitly are unique and start from [`BUILDER_TAGS_START`],
+	/// so they don't collide with small tags set by hand (e.g. `.tag(1)`).
+	/// ```
+	/// use rust_sc2::prelude::*;
+	///
+	/// let marine = Unit::builder(UnitTypeId::Marine).pos(Point2::new(10.0, 10.0)).build();
+	/// let zergling = Unit::builder(UnitTypeId::Zergling)
+	///     .pos(Point2::new(12.0, 10.0))
+	///     .health(28)
+	///     .alliance(Alliance::Enemy)
+	///     .build();
+	///
+	/// assert!(marine.can_attack_unit(&zergling));
+	/// assert!(marine.in_real_range(&zergling, 0.0));
+	/// assert!(!zergling.in_real_range(&marine, 0.0));
+	/// assert_eq!(zergling.health_percentage(), Some(0.8));
+	/// ```
+	pub fn builder(type_id: UnitTypeId) -> UnitBuilder {
+		UnitBuilder::new(type_id)
+	}
+}
+
+/// Builder of [`Unit`], made with [`Unit::builder`].
+pub struct UnitBuilder {
+	base: UnitBase,
+	owner: Option<u32>,
+	weapon_cooldown: Option<f32>,
+}
+impl UnitBuilder {
+	fn new(type_id: UnitTypeId) -> Self {
+		let info = unit_info(type_id);
+		let health = info.map(|i| i.health);
+		let shield = info.filter(|i| i.shield > 0).map(|i| i.shield);
+		Self {
+			base: UnitBase {
+				display_type: Rl::new(DisplayType::Visible),
+				alliance: Alliance::Own,
+				tag: NEXT_TAG.fetch_add(1, Ordering::Relaxed),
+				type_id: Rl::new(type_id),
+				raw_type_id: type_id.to_u32().unwrap_or_default(),
+				owner: 0,
+				position: Point2::default(),
+				position3d: Point3::default(),
+				facing: 0.0,
+				radius: info.map_or(0.5, |i| i.radius),
+				build_progress: 1.0,
+				is_cloaked: LockBool::new(false),
+				is_revealed: LockBool::new(false),
+				buffs: Default::default(),
+				detect_range: 0.0,
+				radar_range: 0.0,
+				is_selected: false,
+				is_on_screen: false,
+				is_blip: false,
+				is_powered: false,
+				is_active: false,
+				attack_upgrade_level: 0,
+				armor_upgrade_level: 0,
+				shield_upgrade_level: 0,
+				health,
+				health_max: health,
+				shield,
+				shield_max: shield,
+				energy: None,
+				energy_max: None,
+				mineral_contents: None,
+				vespene_contents: None,
+				is_flying: info.is_some_and(|i| i.is_flying),
+				is_burrowed: LockBool::new(false),
+				is_hallucination: LockBool::new(false),
+				orders: vec![],
+				addon_tag: None,
+				passengers: vec![],
+				cargo_space_taken: None,
+				cargo_space_max: None,
+				assigned_harvesters: None,
+				ideal_harvesters: None,
+				weapon_cooldown: None,
+				engaged_target_tag: None,
+				buff_duration_remain: None,
+				buff_duration_max: None,
+				rally_targets: vec![],
+
+				// cache
+				real_speed: Default::default(),
+				real_weapon_vs: Default::default(),
+			},
+			owner: None,
+			weapon_cooldown: None,
+		}
+	}
+
+	/// Sets unique tag of unit, it should be below [`BUILDER_TAGS_START`] to not collide with automatic ones.
+	pub fn tag(mut self, tag: u64) -> Self {
+		self.base.tag = tag;
+		self
+	}
+	/// Sets position of unit on the ground.
+	pub fn pos(mut self, pos: Point2) -> Self {
+		self.base.position = pos;
+		self.base.position3d = pos.to3(self.base.position3d.z);
+		self
+	}
+	/// Sets position of unit with height.
+	pub fn pos3(mut self, pos: Point3) -> Self {
+		self.base.position = pos.to2();
+		self.base.position3d = pos;
+		self
+	}
+	/// Sets direction unit faces in radians. Default is `0`.
+	pub fn facing(mut self, facing: f32) -> Self {
+		self.base.facing = facing;
+		self
+	}
+	/// Sets alliance of unit. Default is `Own`.
+	///
+	/// Owner is set by alliance, unless given explicitly:
+	/// `1` for own units, `2` for enemies, `3` for allies and `16` for neutral ones.
+	pub fn alliance(mut self, alliance: Alliance) -> Self {
+		self.base.alliance = alliance;
+		self
+	}
+	/// Sets id of player who owns unit.
+	pub fn owner(mut self, owner: u32) -> Self {
+		self.owner = Some(owner);
+		self
+	}
+	/// Sets current health, maximum is taken from static data.
+	pub fn health(mut self, health: u32) -> Self {
+		self.base.health = Some(health);
+		self
+	}
+	/// Sets maximum health.
+	pub fn health_max(mut self, health_max: u32) -> Self {
+		self.base.health_max = Some(health_max);
+		self
+	}
+	/// Sets current shield, maximum is taken from static data.
+	pub fn shield(mut self, shield: u32) -> Self {
+		self.base.shield = Some(shield);
+		self
+	}
+	/// Sets maximum shield.
+	pub fn shield_max(mut self, shield_max: u32) -> Self {
+		self.base.shield_max = Some(shield_max);
+		self
+	}
+	/// Sets current and maximum energy. Default is `None`.
+	pub fn energy(mut self, energy: u32, energy_max: u32) -> Self {
+		self.base.energy = Some(energy);
+		self.base.energy_max = Some(energy_max);
+		self
+	}
+	/// Sets construction progress in range from `0` to `1`. Default is `1`.
+	pub fn build_progress(mut self, build_progress: f32) -> Self {
+		self.base.build_progress = build_progress;
+		self
+	}
+	/// Sets how unit is displayed. Default is `Visible`.
+	pub fn display_type(mut self, display_type: DisplayType) -> Self {
+		self.base.display_type = Rl::new(display_type);
+		self
+	}
+	/// Adds buff to unit.
+	pub fn buff(mut self, buff: BuffId) -> Self {
+		self.base.buffs.insert(buff);
+		self
+	}
+	/// Makes unit flying.
+	pub fn flying(mut self) -> Self {
+		self.base.is_flying = true;
+		self
+	}
+	/// Makes unit burrowed, burrowed units are also cloaked.
+	pub fn burrowed(mut self) -> Self {
+		self.base.is_burrowed = LockBool::new(true);
+		self.base.is_cloaked = LockBool::new(true);
+		self
+	}
+	/// Makes unit cloaked, `revealed` means that it's detected.
+	pub fn cloaked(mut self, revealed: bool) -> Self {
+		self.base.is_cloaked = LockBool::new(true);
+		self.base.is_revealed = LockBool::new(revealed);
+		self
+	}
+	/// Makes unit hallucination.
+	pub fn hallucination(mut self) -> Self {
+		self.base.is_hallucination = LockBool::new(true);
+		self
+	}
+	/// Makes structure powered by pylon.
+	pub fn powered(mut self) -> Self {
+		self.base.is_powered = true;
+		self
+	}
+	/// Sets detection range of unit.
+	pub fn detect_range(mut self, range: f32) -> Self {
+		self.base.detect_range = range;
+		self
+	}
+	/// Sets upgrade levels of unit.
+	pub fn upgrade_levels(mut self, attack: u32, armor: i32, shield: i32) -> Self {
+		self.base.attack_upgrade_level = attack;
+		self.base.armor_upgrade_level = armor;
+		self.base.shield_upgrade_level = shield;
+		self
+	}
+	/// Adds order to the queue of unit.
+	pub fn order(mut self, ability: AbilityId, target: Target, progress: f32) -> Self {
+		self.base.orders.push(UnitOrder {
+			ability,
+			raw_ability: ability.to_u32().unwrap_or_default(),
+			target,
+			progress,
+		});
+		self
+	}
+	/// Sets weapon cooldown in game loops.
+	/// Default is `0` for own units and `None` for others, as the game does.
+	pub fn weapon_cooldown(mut self, cooldown: f32) -> Self {
+		self.weapon_cooldown = Some(cooldown);
+		self
+	}
+	/// Sets tag of unit, which this unit attacks.
+	pub fn engaged_target(mut self, tag: u64) -> Self {
+		self.base.engaged_target_tag = Some(tag);
+		self
+	}
+	/// Sets tag of addon attached to structure.
+	pub fn addon_tag(mut self, tag: u64) -> Self {
+		self.base.addon_tag = Some(tag);
+		self
+	}
+	/// Sets resources left in mineral field or vespene geyser.
+	pub fn resources(mut self, minerals: u32, vespene: u32) -> Self {
+		self.base.mineral_contents = Some(minerals);
+		self.base.vespene_contents = Some(vespene);
+		self
+	}
+	/// Sets assigned and ideal harvesters of townhall or gas building.
+	pub fn harvesters(mut self, assigned: u32, ideal: u32) -> Self {
+		self.base.assigned_harvesters = Some(assigned);
+		self.base.ideal_harvesters = Some(ideal);
+		self
+	}
+	/// Adds passenger to transport or bunker.
+	pub fn passenger(mut self, passenger: PassengerUnit) -> Self {
+		self.base.passengers.push(passenger);
+		self
+	}
+	/// Sets taken and maximum cargo space of transport or bunker.
+	pub fn cargo(mut self, taken: u32, max: u32) -> Self {
+		self.base.cargo_space_taken = Some(taken);
+		self.base.cargo_space_max = Some(max);
+		self
+	}
+	/// Adds rally target to structure.
+	pub fn rally(mut self, point: Point2, tag: Option<u64>) -> Self {
+		self.base.rally_targets.push(RallyTarget { point, tag });
+		self
+	}
+
+	/// Builds unit.
+	pub fn build(self) -> Unit {
+		let mut base = self.base;
+		let is_mine = base.alliance.is_mine();
+		base.owner = self.owner.unwrap_or(match base.alliance {
+			Alliance::Own => 1,
+			Alliance::Enemy => 2,
+			Alliance::Ally => 3,
+			Alliance::Neutral => 16,
+		});
+		base.weapon_cooldown = self.weapon_cooldown.or(if is_mine { Some(0.0) } else { None });
+		Unit {
+			data: Default::default(),
+			base: Rs::new(base),
+		}
+	}
+}
+
 /// The display type of [`Unit`].
 /// Can be accessed through [`display_type`](Unit::display_type) field.
 #[variant_checkers]
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum DisplayType {
 	/// Fully visible.
 	Visible,
@@ -2086,9 +2532,13 @@ impl FromProto<ProtoDisplayType> for DisplayType {
 
 /// Order given to unit. All current orders of unit stored in [`orders`](Unit::orders) field.
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct UnitOrder {
 	/// Ability unit is using.
+	/// `NullNull` if ability is unknown, its id is kept in [`raw_ability`](Self::raw_ability).
 	pub ability: AbilityId,
+	/// Raw id of ability unit is using.
+	pub raw_ability: u32,
 	/// Target of unit's ability.
 	pub target: Target,
 	/// Progress of train abilities. Value in range from `0` to `1`.
@@ -2097,6 +2547,7 @@ pub struct UnitOrder {
 
 /// Unit inside transport or bunker. All passengers stored in [`passengers`](Unit::passengers) field.
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct PassengerUnit {
 	pub tag: u64,
 	pub health: f32,
@@ -2105,12 +2556,16 @@ pub struct PassengerUnit {
 	pub shield_max: f32,
 	pub energy: f32,
 	pub energy_max: f32,
+	/// `NotAUnit` if type is unknown, its id is kept in [`raw_type_id`](Self::raw_type_id).
 	pub type_id: UnitTypeId,
+	/// Raw id of passenger's type.
+	pub raw_type_id: u32,
 }
 
 /// Rally point of production building.
 /// All rally points stored in [`rally_targets`](Unit::rally_targets) field.
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct RallyTarget {
 	/// Rally point. Position building rallied on.
 	pub point: Point2,
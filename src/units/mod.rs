@@ -8,6 +8,8 @@ use indexmap::{
 };
 use iter::IntoUnits;
 use rustc_hash::FxHasher;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use std::{
 	hash::BuildHasherDefault,
 	iter::FromIterator,
@@ -24,6 +26,7 @@ type FxIndexMap<K, V> = IndexMap<K, V, BuildHasherDefault<FxHasher>>;
 ///
 /// [`units`]: crate::bot::Bot::units
 #[derive(Default, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct AllUnits {
 	/// All the units including owned, enemies and neutral.
 	pub all: Units,
@@ -63,6 +66,7 @@ impl AllUnits {
 
 /// Structured player units (yours or opponent's).
 #[derive(Default, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct PlayerUnits {
 	/// All player units (includes both units and structures).
 	pub all: Units,
@@ -98,6 +102,7 @@ impl PlayerUnits {
 ///
 // [`Unit`]: crate::unit::Unit
 #[derive(Default, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Units(FxIndexMap<u64, Unit>);
 impl Units {
 	// HashMap methods
@@ -0,0 +1,100 @@
+//! Finder of positions to warp in protoss units.
+//!
+//! [`Bot::warp_in_positions`] searches free spots in
+//! [`psionic_matrix`](crate::game_state::RawData::psionic_matrix)
+//! of pylons and phasing warp prisms:
+//! ```
+//! use rust_sc2::prelude::*;
+//!
+//! #[bot]
+//! #[derive(Default)]
+//! struct MyBot;
+//! impl MyBot {
+//!     fn warp_stalkers(&mut self) {
+//!         let gates = self.units.my.structures.ready().of_type(UnitTypeId::WarpGate);
+//!         let ready = gates.filter(|g| g.has_ability(AbilityId::WarpGateTrainStalker));
+//!         let positions = self.warp_in_positions(UnitTypeId::Stalker, self.enemy_start, ready.len());
+//!         for (gate, pos) in ready.iter().zip(positions) {
+//!             gate.warp_in(UnitTypeId::Stalker, pos);
+//!         }
+//!     }
+//! }
+//! ```
+
+use crate::{bot::Bot, distance::*, game_state::PsionicMatrix, geometry::Point2, ids::UnitTypeId};
+
+/// Radius of unit which can be warped in.
+fn warp_in_radius(unit: UnitTypeId) -> f32 {
+	match unit {
+		UnitTypeId::Stalker => 0.625,
+		UnitTypeId::HighTemplar | UnitTypeId::DarkTemplar => 0.375,
+		_ => 0.5,
+	}
+}
+
+impl Bot {
+	/// Returns up to `count` positions, where given unit can be warped in.
+	///
+	/// Positions are searched in powered fields of pylons and phasing warp prisms,
+	/// starting from the field closest to `near` and preferring spots closer to it.
+	/// Each position is visible, pathable, not occupied by other units and structures
+	/// and far enough from other returned positions, so warped units won't collide.
+	pub fn warp_in_positions(&self, unit: UnitTypeId, near: Point2, count: usize) -> Vec<Point2> {
+		let radius = warp_in_radius(unit);
+		let obstacles = self.units.all.filter(|u| !u.is_flying());
+
+		let mut fields = self
+			.state
+			.observation
+			.raw
+			.psionic_matrix
+			.iter()
+			.collect::<Vec<_>>();
+		fields.sort_unstable_by(|a, b| {
+			a.pos
+				.distance_squared(near)
+				.partial_cmp(&b.pos.distance_squared(near))
+				.unwrap()
+		});
+
+		let mut positions = Vec::<Point2>::with_capacity(count);
+		for field in fields {
+			if positions.len() >= count {
+				break;
+			}
+
+			let nearby = obstacles.filter(|u| u.is_closer(field.radius + u.radius() + radius, field.pos));
+			let mut candidates = field_cells(field)
+				.filter(|p| {
+					self.is_pathable(*p)
+						&& self.is_visible(*p)
+						&& !nearby.iter().any(|u| u.is_closer(u.radius() + radius, *p))
+				})
+				.collect::<Vec<_>>();
+			candidates.sort_unstable_by(|a, b| {
+				a.distance_squared(near)
+					.partial_cmp(&b.distance_squared(near))
+					.unwrap()
+			});
+
+			for p in candidates {
+				if positions.len() >= count {
+					break;
+				}
+				if positions.iter().all(|other| other.is_further(radius * 2.0, p)) {
+					positions.push(p);
+				}
+			}
+		}
+		positions
+	}
+}
+
+/// Centers of map cells covered by given psionic matrix.
+fn field_cells(field: &PsionicMatrix) -> impl Iterator<Item = Point2> + '_ {
+	let r = field.radius.ceil() as isize;
+	let center = field.pos.floor();
+	(-r..=r)
+		.flat_map(move |dx| (-r..=r).map(move |dy| center.offset(dx as f32 + 0.5, dy as f32 + 0.5)))
+		.filter(move |p| field.pos.is_closer(field.radius, *p))
+}
@@ -0,0 +1,226 @@
+//! Management of zerg's larva and queen injects.
+//!
+//! Bot keeps [`ZergManager`] in [`zerg`](crate::bot::Bot::zerg) field,
+//! which pairs queens to townhalls, tracks inject timers, larva of each townhall and units in eggs.
+//! ```
+//! use rust_sc2::prelude::*;
+//!
+//! #[bot]
+//! #[derive(Default)]
+//! struct MyBot;
+//! impl MyBot {
+//!     fn on_step(&mut self) {
+//!         self.inject_larva();
+//!
+//!         while self.can_afford(UnitTypeId::Zergling, true) {
+//!             match self.take_larva() {
+//!                 Some(larva) => {
+//!                     larva.train(UnitTypeId::Zergling, false);
+//!                     self.subtract_resources(UnitTypeId::Zergling, true);
+//!                 }
+//!                 None => break,
+//!             }
+//!         }
+//!     }
+//! }
+//! ```
+
+use crate::{
+	action::Target,
+	bot::Bot,
+	consts::LARVA_UNITS,
+	ids::{AbilityId, BuffId, UnitTypeId},
+	unit::Unit,
+	units::Units,
+};
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::mem;
+
+/// Duration of queen's inject in game loops.
+pub const INJECT_DURATION: u32 = 650;
+
+/// Manager of queens, injects and larva.
+#[derive(Clone)]
+pub struct ZergManager {
+	/// Free queens are paired to townhalls without queens automatically. [Default: `true`]
+	///
+	/// Queens claimed in [`roles`](crate::bot::Bot::roles) aren't paired.
+	pub auto_pair: bool,
+	/// Energy queens keep for other abilities besides inject cost. [Default: `0`]
+	pub inject_energy_reserve: u32,
+	pairs: FxHashMap<u64, u64>,
+	injects: FxHashMap<u64, u32>,
+	larva: FxHashMap<u64, Units>,
+	eggs: FxHashMap<UnitTypeId, usize>,
+	taken_larva: FxHashSet<u64>,
+	game_loop: u32,
+}
+impl Default for ZergManager {
+	fn default() -> Self {
+		Self {
+			auto_pair: true,
+			inject_energy_reserve: 0,
+			pairs: Default::default(),
+			injects: Default::default(),
+			larva: Default::default(),
+			eggs: Default::default(),
+			taken_larva: Default::default(),
+			game_loop: 0,
+		}
+	}
+}
+impl ZergManager {
+	/// Pairs queen with given tag to townhall with given tag.
+	/// If townhall already had a queen, that queen becomes unpaired.
+	pub fn pair(&mut self, queen: u64, townhall: u64) {
+		self.pairs.retain(|_, th| *th != townhall);
+		self.pairs.insert(queen, townhall);
+	}
+	/// Unpairs queen with given tag, returns tag of townhall it was paired to.
+	pub fn unpair(&mut self, queen: u64) -> Option<u64> {
+		self.pairs.remove(&queen)
+	}
+	/// Returns tag of townhall given queen is paired to.
+	pub fn townhall_of(&self, queen: u64) -> Option<u64> {
+		self.pairs.get(&queen).copied()
+	}
+	/// Returns tag of queen paired to given townhall.
+	pub fn queen_of(&self, townhall: u64) -> Option<u64> {
+		self.pairs
+			.iter()
+			.find(|(_, th)| **th == townhall)
+			.map(|(queen, _)| *queen)
+	}
+	/// Iterates over pairs of (queen tag, townhall tag).
+	pub fn pairs(&self) -> impl Iterator<Item = (u64, u64)> + '_ {
+		self.pairs.iter().map(|(queen, th)| (*queen, *th))
+	}
+
+	/// Returns game loops left until inject of given townhall pops larva,
+	/// `None` if townhall isn't injected.
+	pub fn inject_remaining(&self, townhall: u64) -> Option<u32> {
+		self.injects
+			.get(&townhall)
+			.map(|start| (start + INJECT_DURATION).saturating_sub(self.game_loop))
+	}
+	/// Checks if given townhall is injected now.
+	pub fn is_injected(&self, townhall: u64) -> bool {
+		self.injects.contains_key(&townhall)
+	}
+
+	/// Returns larva of given townhall.
+	pub fn larva_of(&self, townhall: u64) -> Option<&Units> {
+		self.larva.get(&townhall)
+	}
+	/// Returns amount of larva of given townhall.
+	pub fn larva_count(&self, townhall: u64) -> usize {
+		self.larva.get(&townhall).map_or(0, |larva| larva.len())
+	}
+	/// Units in eggs counted by unit type (zerglings are counted in pairs).
+	pub fn eggs(&self) -> &FxHashMap<UnitTypeId, usize> {
+		&self.eggs
+	}
+	/// Returns amount of units of given type in eggs.
+	pub fn egg_count(&self, unit: UnitTypeId) -> usize {
+		self.eggs.get(&unit).copied().unwrap_or(0)
+	}
+
+	/// Returns larva from townhall with the most free larva,
+	/// so larva is used evenly and townhalls don't stay on larva cap.
+	/// Taken larva isn't returned again until next step.
+	pub fn take_larva(&mut self) -> Option<Unit> {
+		let taken = &self.taken_larva;
+		let larva = self
+			.larva
+			.values()
+			.map(|larva| larva.filter(|l| !taken.contains(&l.tag())))
+			.max_by_key(|larva| larva.len())?
+			.first()
+			.cloned()?;
+		self.taken_larva.insert(larva.tag());
+		Some(larva)
+	}
+
+	pub(crate) fn update(&mut self, bot: &Bot) {
+		let game_loop = bot.state.observation.game_loop();
+		self.game_loop = game_loop;
+		self.taken_larva.clear();
+
+		let units = &bot.units.my;
+		let townhalls = units.townhalls.filter(|th| th.is_ready());
+
+		// Pairs
+		self.pairs
+			.retain(|queen, th| units.units.contains_tag(*queen) && townhalls.contains_tag(*th));
+		if self.auto_pair {
+			for queen in units.units.of_type(UnitTypeId::Queen).iter() {
+				if self.pairs.contains_key(&queen.tag()) || bot.roles.is_claimed(queen.tag()) {
+					continue;
+				}
+				let paired = self.pairs.values().copied().collect::<FxHashSet<_>>();
+				if let Some(th) = townhalls.filter(|th| !paired.contains(&th.tag())).closest(queen) {
+					self.pairs.insert(queen.tag(), th.tag());
+				}
+			}
+		}
+
+		// Inject timers
+		let old = mem::take(&mut self.injects);
+		for th in &townhalls {
+			if th.has_buff(BuffId::QueenSpawnLarvaTimer) {
+				let start = old.get(&th.tag()).copied().unwrap_or_else(|| {
+					match (th.buff_duration_remain(), th.buff_duration_max()) {
+						(Some(remain), Some(max)) if max == INJECT_DURATION => {
+							game_loop.saturating_sub(max - remain.min(max))
+						}
+						_ => game_loop,
+					}
+				});
+				self.injects.insert(th.tag(), start);
+			}
+		}
+
+		// Larva of townhalls
+		self.larva.clear();
+		for larva in &units.larvas {
+			if let Some(th) = townhalls.closest(larva) {
+				self.larva.entry(th.tag()).or_default().push(larva.clone());
+			}
+		}
+
+		// Units in eggs
+		self.eggs.clear();
+		for egg in units.all.of_type(UnitTypeId::Egg).iter() {
+			if let Some((unit, amount)) = egg.ordered_ability().and_then(|a| LARVA_UNITS.get(&a)) {
+				*self.eggs.entry(*unit).or_default() += amount;
+			}
+		}
+	}
+}
+
+impl Bot {
+	/// Orders paired queens to inject their townhalls when they have enough energy
+	/// and townhalls aren't injected already.
+	pub fn inject_larva(&self) {
+		let reserve = self.zerg.inject_energy_reserve;
+		for (queen, th) in self.zerg.pairs() {
+			let queen = match self.units.my.units.get(queen) {
+				Some(queen) => queen,
+				None => continue,
+			};
+			if self.zerg.is_injected(th)
+				|| queen.is_using(AbilityId::EffectInjectLarva)
+				|| !queen.can_cast(AbilityId::EffectInjectLarva)
+				|| !matches!(queen.energy(), Some(energy) if energy >= 25 + reserve)
+			{
+				continue;
+			}
+			queen.command(AbilityId::EffectInjectLarva, Target::Tag(th), false);
+		}
+	}
+	/// Takes free larva evenly from all townhalls.
+	/// See [`ZergManager::take_larva`] for details.
+	pub fn take_larva(&mut self) -> Option<Unit> {
+		self.zerg.take_larva()
+	}
+}
@@ -0,0 +1,72 @@
+use rust_sc2::{
+	aoe::AoeSpell,
+	game_data::{AbilityData, AbilityTarget, EffectData, GameData, TargetType},
+	prelude::*,
+};
+
+#[test]
+fn radius_from_game_data() {
+	let mut game_data = GameData::default();
+	assert_eq!(AoeSpell::PsiStorm.radius(&game_data), 1.5);
+	assert_eq!(AoeSpell::FungalGrowth.radius(&game_data), 2.25);
+
+	game_data.effects.insert(
+		EffectId::PsiStormPersistent,
+		EffectData {
+			id: EffectId::PsiStormPersistent,
+			name: "PsiStormPersistent".to_string(),
+			friendly_name: "Psionic Storm".to_string(),
+			radius: 1.75,
+			target: TargetType::Any,
+			friendly_fire: true,
+		},
+	);
+	game_data.abilities.insert(
+		AbilityId::FungalGrowthFungalGrowth,
+		AbilityData {
+			id: AbilityId::FungalGrowthFungalGrowth,
+			link_name: "FungalGrowth".to_string(),
+			link_index: 0,
+			button_name: None,
+			friendly_name: None,
+			hotkey: None,
+			remaps_to_ability_id: None,
+			available: true,
+			target: AbilityTarget::Point,
+			allow_minimap: false,
+			allow_autocast: false,
+			is_building: false,
+			footprint_radius: Some(2.5),
+			is_instant_placement: false,
+			cast_range: Some(10.0),
+			cooldown: None,
+			energy_cost: None,
+		},
+	);
+	assert_eq!(AoeSpell::PsiStorm.radius(&game_data), 1.75);
+	assert_eq!(AoeSpell::FungalGrowth.radius(&game_data), 2.5);
+	assert_eq!(AoeSpell::EMP.radius(&game_data), 1.5);
+}
+
+#[test]
+fn best_aoe_unit_picks_primary_target() {
+	let enemy = |type_id, x| {
+		Unit::builder(type_id)
+			.pos(Point2::new(x, 10.0))
+			.alliance(Alliance::Enemy)
+			.build()
+	};
+	let lone = enemy(UnitTypeId::Marine, 2.0);
+	let clump = [
+		enemy(UnitTypeId::Marine, 20.0),
+		enemy(UnitTypeId::Marine, 21.0),
+		enemy(UnitTypeId::Marine, 22.0),
+	];
+	let clump_tags = clump.iter().map(|u| u.tag()).collect::<Vec<_>>();
+	let units = std::iter::once(lone).chain(clump).collect::<Units>();
+
+	let (tag, value) = units.best_aoe_unit(1.25, |_| 50.0, |_| true).unwrap();
+	assert_eq!(tag, clump_tags[1]);
+	assert_eq!(value, 150.0);
+	assert!(units.best_aoe_unit(1.25, |_| 50.0, |_| false).is_none());
+}
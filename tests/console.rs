@@ -0,0 +1,22 @@
+use rust_sc2::{console::Console, prelude::*};
+
+#[test]
+fn only_allowed_players() {
+	let mut console = Console::default();
+	assert!(console.is_allowed(1, 1));
+	assert!(!console.is_allowed(2, 1));
+
+	console.allowed_players = Some(vec![2]);
+	assert!(!console.is_allowed(1, 1));
+	assert!(console.is_allowed(2, 1));
+}
+
+#[test]
+fn unit_type_by_name() {
+	let console = Console::default();
+	let (command, args) = console.parse("!spawn siegetank 5").unwrap().unwrap();
+	assert_eq!(command.name, "spawn");
+	assert_eq!(args.unit_type(0), Some(UnitTypeId::SiegeTank));
+	assert_eq!(args.int(1), Some(5));
+	assert!(console.parse("!spawn notaunitname").unwrap().is_err());
+}
@@ -0,0 +1,111 @@
+use rust_sc2::{prelude::*, snapshot::Snapshot};
+
+fn enemy(type_id: UnitTypeId, x: f32, y: f32) -> Unit {
+	Unit::builder(type_id)
+		.pos(Point2::new(x, y))
+		.alliance(Alliance::Enemy)
+		.build()
+}
+
+#[test]
+fn builder_defaults() {
+	let stalker = Unit::builder(UnitTypeId::Stalker).build();
+	assert!(stalker.is_mine());
+	assert_eq!(stalker.owner(), 1);
+	assert_eq!(stalker.health(), Some(80));
+	assert_eq!(stalker.shield(), Some(80));
+	assert!(stalker.is_ready());
+	assert!(stalker.is_idle());
+	assert_eq!(stalker.weapon_cooldown(), Some(0.0));
+
+	let zergling = enemy(UnitTypeId::Zergling, 0.0, 0.0);
+	assert_eq!(zergling.owner(), 2);
+	assert_eq!(zergling.weapon_cooldown(), None);
+	assert_ne!(stalker.tag(), zergling.tag());
+}
+
+#[test]
+fn units_helpers() {
+	let units: Units = [
+		Unit::builder(UnitTypeId::Marine)
+			.pos(Point2::new(10.0, 10.0))
+			.health(20)
+			.build(),
+		Unit::builder(UnitTypeId::Marine)
+			.pos(Point2::new(15.0, 10.0))
+			.build(),
+		Unit::builder(UnitTypeId::SCV)
+			.pos(Point2::new(30.0, 30.0))
+			.order(AbilityId::HarvestGatherSCV, Target::Tag(1), 0.0)
+			.build(),
+	]
+	.into_iter()
+	.collect();
+
+	assert_eq!(units.of_type(UnitTypeId::Marine).len(), 2);
+	assert_eq!(units.idle().len(), 2);
+	assert_eq!(units.closer(6.0, Point2::new(10.0, 10.0)).len(), 2);
+	assert_eq!(units.closest(Point2::new(14.0, 10.0)).unwrap().health(), Some(45));
+	assert!(units.iter().any(|u| u.is_gathering()));
+
+	let zergling = enemy(UnitTypeId::Zergling, 12.0, 10.0);
+	assert_eq!(units.in_range(&zergling, 0.0).len(), 2);
+	assert_eq!(units.in_range_of(&zergling, 0.0).len(), 0);
+}
+
+#[test]
+fn air_units() {
+	let mutalisk = enemy(UnitTypeId::Mutalisk, 12.0, 10.0);
+	let zealot = Unit::builder(UnitTypeId::Zealot)
+		.pos(Point2::new(10.0, 10.0))
+		.build();
+	let marine = Unit::builder(UnitTypeId::Marine)
+		.pos(Point2::new(10.0, 10.0))
+		.build();
+
+	assert!(mutalisk.is_flying());
+	assert!(!zealot.can_attack_unit(&mutalisk));
+	assert!(marine.can_attack_unit(&mutalisk));
+	assert!(marine.in_real_range(&mutalisk, 0.0));
+	assert!(mutalisk.can_attack_unit(&zealot));
+	assert!(Unit::builder(UnitTypeId::BarracksFlying).build().is_flying());
+	assert!(!Unit::builder(UnitTypeId::VikingAssault).build().is_flying());
+}
+
+#[test]
+fn synthetic_bot() {
+	let bot = Snapshot::new(Race::Protoss, 64, 64)
+		.units([
+			Unit::builder(UnitTypeId::Nexus)
+				.pos(Point2::new(20.5, 20.5))
+				.build(),
+			Unit::builder(UnitTypeId::Pylon)
+				.pos(Point2::new(25.0, 25.0))
+				.build_progress(0.5)
+				.build(),
+			Unit::builder(UnitTypeId::Probe)
+				.pos(Point2::new(22.0, 22.0))
+				.build(),
+			enemy(UnitTypeId::Zealot, 40.0, 40.0),
+			Unit::builder(UnitTypeId::MineralField)
+				.pos(Point2::new(14.0, 20.5))
+				.alliance(Alliance::Neutral)
+				.resources(1800, 0)
+				.build(),
+		])
+		.minerals(150)
+		.supply(13, 15)
+		.game_loop(224)
+		.bot();
+
+	assert_eq!(bot.units.my.townhalls.len(), 1);
+	assert_eq!(bot.units.my.workers.len(), 1);
+	assert_eq!(bot.units.my.structures.not_ready().len(), 1);
+	assert_eq!(bot.units.enemy.units.len(), 1);
+	assert_eq!(bot.units.mineral_fields.len(), 1);
+	assert_eq!(bot.supply_left, 2);
+	assert_eq!(bot.time, 10.0);
+	assert!(bot.can_afford(UnitTypeId::Probe, true));
+	assert!(bot.is_pathable(Point2::new(40.0, 40.0)));
+	assert!(bot.is_visible(Point2::new(40.0, 40.0)));
+}
@@ -0,0 +1,112 @@
+#![cfg(feature = "serde")]
+
+use rust_sc2::{bot::Bot, prelude::*, snapshot::Snapshot};
+
+/// Checks that snapshot with 75 minerals and own marine with tag `42` at (10, 12) survives serialization.
+fn assert_marine_round_trip(snapshot: &Snapshot) {
+	let json = serde_json::to_string(snapshot).unwrap();
+	let bot = Bot::from_snapshot(serde_json::from_str(&json).unwrap());
+	assert_eq!(bot.minerals, 75);
+	assert_eq!(bot.units.my.units.len(), 1);
+
+	let marine = bot.units.my.units.first().unwrap();
+	assert_eq!(marine.tag(), 42);
+	assert_eq!(marine.type_id(), UnitTypeId::Marine);
+	assert_eq!(marine.position(), Point2::new(10.0, 12.0));
+	// Game data is empty, so static data is used instead
+	assert_eq!(marine.cost().minerals, 50);
+	assert!(marine.can_attack_air());
+
+	let again = serde_json::to_value(bot.snapshot()).unwrap();
+	assert_eq!(
+		again["units"]["42"],
+		serde_json::from_str::<serde_json::Value>(&json).unwrap()["units"]["42"]
+	);
+}
+
+#[test]
+fn empty_snapshot_round_trip() {
+	let snapshot = Bot::default().snapshot();
+	let json = serde_json::to_string(&snapshot).unwrap();
+	let bot = Bot::from_snapshot(serde_json::from_str::<Snapshot>(&json).unwrap());
+	assert!(bot.units.all.is_empty());
+	assert_eq!(bot.game_step(), 1);
+}
+
+#[test]
+fn units_survive_round_trip() {
+	let mut snapshot = Snapshot {
+		player_id: 1,
+		race: Race::Terran,
+		..Default::default()
+	};
+	snapshot.state.observation.common.minerals = 75;
+	snapshot.units = serde_json::from_str(
+		r#"{
+			"42": {
+				"display_type": "Visible",
+				"alliance": "Own",
+				"tag": 42,
+				"type_id": "Marine",
+				"raw_type_id": 48,
+				"owner": 1,
+				"position": {"x": 10.0, "y": 12.0},
+				"position3d": {"x": 10.0, "y": 12.0, "z": 0.0},
+				"facing": 0.0,
+				"radius": 0.375,
+				"build_progress": 1.0,
+				"is_cloaked": false,
+				"is_revealed": false,
+				"buffs": [],
+				"detect_range": 0.0,
+				"radar_range": 0.0,
+				"is_selected": false,
+				"is_on_screen": false,
+				"is_blip": false,
+				"is_powered": false,
+				"is_active": false,
+				"attack_upgrade_level": 0,
+				"armor_upgrade_level": 0,
+				"shield_upgrade_level": 0,
+				"health": 45,
+				"health_max": 45,
+				"shield": null,
+				"shield_max": null,
+				"energy": null,
+				"energy_max": null,
+				"mineral_contents": null,
+				"vespene_contents": null,
+				"is_flying": false,
+				"is_burrowed": false,
+				"is_hallucination": false,
+				"orders": [],
+				"addon_tag": null,
+				"passengers": [],
+				"cargo_space_taken": null,
+				"cargo_space_max": null,
+				"assigned_harvesters": null,
+				"ideal_harvesters": null,
+				"weapon_cooldown": 0.0,
+				"engaged_target_tag": null,
+				"buff_duration_remain": null,
+				"buff_duration_max": null,
+				"rally_targets": []
+			}
+		}"#,
+	)
+	.unwrap();
+
+	assert_marine_round_trip(&snapshot);
+}
+
+#[test]
+fn built_units_survive_round_trip() {
+	let snapshot = Snapshot::new(Race::Terran, 32, 32)
+		.minerals(75)
+		.units([Unit::builder(UnitTypeId::Marine)
+			.tag(42)
+			.pos(Point2::new(10.0, 12.0))
+			.build()]);
+
+	assert_marine_round_trip(&snapshot);
+}
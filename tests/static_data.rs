@@ -0,0 +1,53 @@
+//! Consistency checks of static data tables.
+//! Values themselves can be compared with the game using `static-data` example.
+
+use rust_sc2::{
+	ids::UnitTypeId,
+	static_data::{unit_info, UNIT_INFO, UPGRADE_INFO},
+};
+
+#[test]
+fn times_are_whole_normal_seconds() {
+	// Normal game speed has 16 game loops per second
+	for (unit, info) in UNIT_INFO.iter() {
+		assert_eq!(info.build_time % 16.0, 0.0, "build time of {:?}", unit);
+	}
+	for (upgrade, info) in UPGRADE_INFO.iter() {
+		assert_eq!(info.research_time % 16.0, 0.0, "research time of {:?}", upgrade);
+	}
+}
+
+#[test]
+fn morphs_include_cost_of_source() {
+	for (morph, source) in [
+		(UnitTypeId::OrbitalCommand, UnitTypeId::CommandCenter),
+		(UnitTypeId::PlanetaryFortress, UnitTypeId::CommandCenter),
+		(UnitTypeId::Lair, UnitTypeId::Hatchery),
+		(UnitTypeId::Hive, UnitTypeId::Lair),
+		(UnitTypeId::GreaterSpire, UnitTypeId::Spire),
+		(UnitTypeId::Overseer, UnitTypeId::Overlord),
+		(UnitTypeId::Ravager, UnitTypeId::Roach),
+		(UnitTypeId::LurkerMP, UnitTypeId::Hydralisk),
+		(UnitTypeId::BroodLord, UnitTypeId::Corruptor),
+	] {
+		let (morph_info, source_info) = (unit_info(morph).unwrap(), unit_info(source).unwrap());
+		assert!(
+			morph_info.mineral_cost >= source_info.mineral_cost
+				&& morph_info.vespene_cost >= source_info.vespene_cost,
+			"{:?} is cheaper than {:?}",
+			morph,
+			source
+		);
+	}
+}
+
+#[test]
+fn air_units_and_structures_dont_take_cargo() {
+	for (unit, info) in UNIT_INFO.iter() {
+		if info.is_flying || info.building_size.is_some() {
+			assert_eq!(info.cargo_size, 0, "cargo of {:?}", unit);
+		}
+	}
+	assert!(unit_info(UnitTypeId::BarracksFlying).unwrap().is_flying);
+	assert_eq!(unit_info(UnitTypeId::HellionTank).unwrap().cargo_size, 2);
+}
@@ -0,0 +1,421 @@
+//! Generation of `src/ids` module from `stableid.json`.
+
+use serde_json::Value;
+use std::collections::HashMap;
+
+const HEAD: &str = "\
+#![allow(deprecated)]
+
+#[cfg(feature = \"serde\")]
+use serde::{Serialize, Deserialize};
+";
+const DERIVES: &str = "\
+#[cfg_attr(feature = \"serde\", derive(Serialize, Deserialize))]
+#[derive(Debug, FromPrimitive, ToPrimitive, Copy, Clone, PartialEq, Eq, Hash)]";
+
+/// Names of enums and files they're written to, in order of generation.
+pub const ENUMS: [(&str, &str); 5] = [
+	("UnitTypeId", "unit_typeid"),
+	("AbilityId", "ability_id"),
+	("UpgradeId", "upgrade_id"),
+	("BuffId", "buff_id"),
+	("EffectId", "effect_id"),
+];
+
+/// Old names kept for compatibility: (enum, old name, new name).
+/// Old names are marked deprecated in favor of new ones.
+pub const MIMICS: &[(&str, &str, &str)] = &[
+	("UnitTypeId", "Lurker", "LurkerMP"),
+	("UnitTypeId", "LurkerBurrowed", "LurkerMPBurrowed"),
+	("UnitTypeId", "LurkerDen", "LurkerDenMP"),
+	("UnitTypeId", "LurkerEgg", "LurkerMPEgg"),
+	(
+		"UpgradeId",
+		"TerranVehicleArmorsLevel1",
+		"TerranVehicleAndShipArmorsLevel1",
+	),
+	(
+		"UpgradeId",
+		"TerranVehicleArmorsLevel2",
+		"TerranVehicleAndShipArmorsLevel2",
+	),
+	(
+		"UpgradeId",
+		"TerranVehicleArmorsLevel3",
+		"TerranVehicleAndShipArmorsLevel3",
+	),
+	(
+		"UpgradeId",
+		"TerranShipArmorsLevel1",
+		"TerranVehicleAndShipArmorsLevel1",
+	),
+	(
+		"UpgradeId",
+		"TerranShipArmorsLevel2",
+		"TerranVehicleAndShipArmorsLevel2",
+	),
+	(
+		"UpgradeId",
+		"TerranShipArmorsLevel3",
+		"TerranVehicleAndShipArmorsLevel3",
+	),
+	("UpgradeId", "MarineStimpack", "Stimpack"),
+	("UpgradeId", "CombatShield", "ShieldWall"),
+	("UpgradeId", "JackhammerConcussionGrenades", "PunisherGrenades"),
+	("UpgradeId", "InfernalPreIgniters", "HighCapacityBarrels"),
+	(
+		"UpgradeId",
+		"HellionCampaignInfernalPreIgniter",
+		"HighCapacityBarrels",
+	),
+	("UpgradeId", "TransformationServos", "SmartServos"),
+	(
+		"UpgradeId",
+		"CycloneRapidFireLaunchers",
+		"CycloneLockOnDamageUpgrade",
+	),
+	("UpgradeId", "MagFieldLaunchers", "CycloneLockOnDamageUpgrade"),
+	("UpgradeId", "PermanentCloakGhost", "PersonalCloaking"),
+	("UpgradeId", "YamatoCannon", "BattlecruiserEnableSpecializations"),
+];
+
+/// Fixes for wrong ids in `stableid.json`: (enum, name, id).
+pub const FIXES: &[(&str, &str, u32)] = &[("AbilityId", "TerranBuildRefinery", 320)];
+
+/// Variants of enum in order of insertion, assigning existing name replaces its id.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Variants {
+	entries: Vec<(String, u32)>,
+	index: HashMap<String, usize>,
+}
+impl Variants {
+	pub fn insert(&mut self, name: String, id: u32) {
+		match self.index.get(&name) {
+			Some(&i) => self.entries[i].1 = id,
+			None => {
+				self.index.insert(name.clone(), self.entries.len());
+				self.entries.push((name, id));
+			}
+		}
+	}
+	pub fn contains(&self, name: &str) -> bool {
+		self.index.contains_key(name)
+	}
+	#[cfg(test)]
+	pub fn get(&self, name: &str) -> Option<u32> {
+		self.index.get(name).map(|&i| self.entries[i].1)
+	}
+	/// Variants sorted by id, variants with equal ids keep order of insertion.
+	pub fn sorted(&self) -> Vec<(&str, u32)> {
+		let mut sorted = self
+			.entries
+			.iter()
+			.map(|(name, id)| (name.as_str(), *id))
+			.collect::<Vec<_>>();
+		sorted.sort_by_key(|(_, id)| *id);
+		sorted
+	}
+}
+
+/// Removes characters not allowed in identifiers.
+fn clean(name: &str) -> String {
+	name.chars().filter(|c| !matches!(c, ' ' | '_' | '@')).collect()
+}
+/// Prefixes names starting with digit with underscore.
+fn prefix_digit(name: String) -> String {
+	match name.chars().next() {
+		Some(c) if c.is_ascii_digit() => format!("_{}", name),
+		_ => name,
+	}
+}
+fn capitalize(name: &str) -> String {
+	let mut chars = name.chars();
+	match chars.next() {
+		Some(first) => first.to_uppercase().chain(chars).collect(),
+		None => String::new(),
+	}
+}
+
+fn str_field<'a>(v: &'a Value, key: &str) -> &'a str {
+	v.get(key).and_then(Value::as_str).unwrap_or_default()
+}
+fn id_field(v: &Value) -> Result<u32, String> {
+	v.get("id")
+		.and_then(Value::as_u64)
+		.and_then(|id| u32::try_from(id).ok())
+		.ok_or_else(|| format!("Missing id: {:?}", v))
+}
+fn section<'a>(data: &'a Value, name: &str) -> Result<&'a [Value], String> {
+	data.get(name)
+		.and_then(Value::as_array)
+		.map(Vec::as_slice)
+		.ok_or_else(|| format!("Missing `{}` section", name))
+}
+
+/// Parses units, upgrades, buffs and effects, named by their `name`.
+/// Duplicate names get numeric suffix starting from `2`.
+pub fn parse_simple(items: &[Value]) -> Result<Variants, String> {
+	let mut variants = Variants::default();
+	for v in items {
+		let name = str_field(v, "name");
+		if name.is_empty() {
+			continue;
+		}
+		let mut name = prefix_digit(clean(name));
+		if variants.contains(&name) {
+			let mut index = 2;
+			while variants.contains(&format!("{}{}", name, index)) {
+				index += 1;
+			}
+			name = format!("{}{}", name, index);
+		}
+		variants.insert(capitalize(&name), id_field(v)?);
+	}
+	Ok(variants)
+}
+
+/// Parses abilities, named by their `name` and `buttonname`, or by `friendlyname` if it's present.
+pub fn parse_abilities(items: &[Value]) -> Result<Variants, String> {
+	let mut variants = Variants::default();
+	for v in items {
+		let mut name = str_field(v, "buttonname").to_string();
+		if name.is_empty() {
+			if v.get("remapid").is_none() {
+				continue;
+			}
+			name = str_field(v, "friendlyname").to_string();
+			if name.is_empty() {
+				return Err(format!("Not mapped: {:?}", v));
+			}
+		}
+
+		name = clean(&name);
+		if let Some(prefix) = v.get("name").and_then(Value::as_str) {
+			name = format!("{}{}", clean(prefix), name);
+		}
+		if let Some(friendly) = v.get("friendlyname").and_then(Value::as_str) {
+			name = clean(friendly);
+		}
+		let name = capitalize(&prefix_digit(name)).replace("ResearchResearch", "Research");
+		variants.insert(name, id_field(v)?);
+	}
+	Ok(variants)
+}
+
+/// Parses all enums from `stableid.json`, in order of [`ENUMS`].
+pub fn parse_data(data: &Value) -> Result<[Variants; 5], String> {
+	let mut enums = [
+		parse_simple(section(data, "Units")?)?,
+		parse_abilities(section(data, "Abilities")?)?,
+		parse_simple(section(data, "Upgrades")?)?,
+		parse_simple(section(data, "Buffs")?)?,
+		parse_simple(section(data, "Effects")?)?,
+	];
+	for (enum_name, name, id) in FIXES {
+		if let Some(i) = ENUMS.iter().position(|(e, _)| e == enum_name) {
+			enums[i].insert(name.to_string(), *id);
+		}
+	}
+	Ok(enums)
+}
+
+fn mimic(enum_name: &str, name: &str) -> String {
+	MIMICS
+		.iter()
+		.find(|(e, old, _)| *e == enum_name && *old == name)
+		.map(|(_, _, new)| {
+			format!(
+				"\t#[deprecated(note = \"Use `{}::{}` instead.\")]\n",
+				enum_name, new
+			)
+		})
+		.unwrap_or_default()
+}
+
+/// Generates source of enum file.
+pub fn gen_enum(name: &str, variants: &Variants) -> String {
+	let mut s = format!("{}\n{}\npub enum {} {{\n", HEAD, DERIVES, name);
+	for (variant, id) in variants.sorted() {
+		s.push_str(&mimic(name, variant));
+		s.push_str(&format!("\t{} = {},\n", variant, id));
+	}
+	s.push_str("}\n");
+	s
+}
+
+/// Generates source of `mod.rs`.
+pub fn gen_mod() -> String {
+	let parts = [
+		vec![
+			"//! Auto generated with `cargo xtask ids` from `stableid.json`".to_string(),
+			"//! ids of units, ablities, upgrades, buffs and effects.".to_string(),
+			"#![allow(missing_docs)]".to_string(),
+		],
+		ENUMS.iter().map(|(_, file)| format!("mod {};", file)).collect(),
+		ENUMS
+			.iter()
+			.map(|(name, file)| format!("pub use {}::{};", file, name))
+			.collect(),
+		[
+			"mod impls;",
+			"mod unknown;",
+			"",
+			"pub use unknown::{unknown_ids, IdKind, UnknownId};",
+			"pub(crate) use unknown::ParseId;",
+		]
+		.iter()
+		.map(|line| line.to_string())
+		.collect(),
+	];
+	parts
+		.iter()
+		.map(|part| part.join("\n"))
+		.collect::<Vec<_>>()
+		.join("\n\n")
+		+ "\n"
+}
+
+/// Generates all files of `ids` module: (file name, source).
+pub fn generate(data: &Value) -> Result<Vec<(String, String)>, String> {
+	let enums = parse_data(data)?;
+	let mut files = ENUMS
+		.iter()
+		.zip(&enums)
+		.map(|((name, file), variants)| (format!("{}.rs", file), gen_enum(name, variants)))
+		.collect::<Vec<_>>();
+	files.push(("mod.rs".to_string(), gen_mod()));
+	Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_json::from_str as parse;
+
+	fn items(json: &str) -> Vec<Value> {
+		parse::<Value>(json).unwrap().as_array().unwrap().to_vec()
+	}
+
+	#[test]
+	fn simple_names_are_cleaned_and_deduplicated() {
+		let variants = parse_simple(&items(
+			r#"[
+				{"id": 0, "name": "NotAUnit"},
+				{"id": 1, "name": ""},
+				{"id": 2, "name": "Siege Tank_Sieged"},
+				{"id": 3, "name": "Beacon@Army"},
+				{"id": 4, "name": "Beacon@Army"},
+				{"id": 5, "name": "BeaconArmy"},
+				{"id": 6, "name": "250mmStrikeCannons"},
+				{"id": 7, "name": "xelNagaTower"}
+			]"#,
+		))
+		.unwrap();
+		assert_eq!(
+			variants.sorted(),
+			[
+				("NotAUnit", 0),
+				("SiegeTankSieged", 2),
+				("BeaconArmy", 3),
+				("BeaconArmy2", 4),
+				("BeaconArmy3", 5),
+				("_250mmStrikeCannons", 6),
+				("XelNagaTower", 7),
+			]
+		);
+	}
+
+	#[test]
+	fn abilities_are_named_like_in_python_generator() {
+		let variants = parse_abilities(&items(
+			r#"[
+				{"buttonname": "Null", "id": 0, "index": 255, "name": "Null"},
+				{"buttonname": "Smart", "friendlyname": "Smart", "id": 1, "index": 255, "name": ""},
+				{"buttonname": "", "id": 3, "index": 1, "name": "Taunt"},
+				{"buttonname": "", "friendlyname": "Attack", "id": 23, "index": 0, "name": "Attack", "remapid": 3674},
+				{"buttonname": "Research", "friendlyname": "Research Research Stuff", "id": 30, "index": 0, "name": "Lab"},
+				{"buttonname": "Stop", "id": 4, "index": 0, "name": "Stop"},
+				{"buttonname": "Stop", "id": 5, "index": 0, "name": "Stop"}
+			]"#,
+		))
+		.unwrap();
+		assert_eq!(variants.get("NullNull"), Some(0));
+		assert_eq!(variants.get("Smart"), Some(1));
+		assert!(!variants.sorted().iter().any(|(_, id)| *id == 3));
+		assert_eq!(variants.get("Attack"), Some(23));
+		assert_eq!(variants.get("ResearchStuff"), Some(30));
+		// Later ability with the same name replaces earlier one
+		assert_eq!(variants.get("StopStop"), Some(5));
+
+		let unmapped = items(r#"[{"buttonname": "", "id": 9, "name": "X", "remapid": 1}]"#);
+		assert!(parse_abilities(&unmapped).is_err());
+	}
+
+	#[test]
+	fn fixes_are_applied() {
+		let data = parse::<Value>(
+			r#"{
+				"Units": [], "Upgrades": [], "Buffs": [], "Effects": [],
+				"Abilities": [{"buttonname": "Refinery", "id": 1, "name": "TerranBuild"}]
+			}"#,
+		)
+		.unwrap();
+		let enums = parse_data(&data).unwrap();
+		assert_eq!(enums[1].get("TerranBuildRefinery"), Some(320));
+	}
+
+	#[test]
+	fn enums_are_sorted_with_deprecated_mimics() {
+		let mut variants = Variants::default();
+		variants.insert("LurkerMP".to_string(), 502);
+		variants.insert("Lurker".to_string(), 911);
+		variants.insert("Zergling".to_string(), 105);
+		assert_eq!(
+			gen_enum("UnitTypeId", &variants),
+			"\
+#![allow(deprecated)]
+
+#[cfg(feature = \"serde\")]
+use serde::{Serialize, Deserialize};
+
+#[cfg_attr(feature = \"serde\", derive(Serialize, Deserialize))]
+#[derive(Debug, FromPrimitive, ToPrimitive, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum UnitTypeId {
+	Zergling = 105,
+	LurkerMP = 502,
+	#[deprecated(note = \"Use `UnitTypeId::LurkerMP` instead.\")]
+	Lurker = 911,
+}
+"
+		);
+	}
+
+	#[test]
+	fn generated_mod_matches_repository() {
+		let current = include_str!("../../src/ids/mod.rs");
+		assert_eq!(gen_mod(), current);
+	}
+
+	#[test]
+	fn mimics_point_to_existing_variants() {
+		let sources = [
+			("UnitTypeId", include_str!("../../src/ids/unit_typeid.rs")),
+			("UpgradeId", include_str!("../../src/ids/upgrade_id.rs")),
+		];
+		for (enum_name, old, new) in MIMICS {
+			let (_, source) = sources.iter().find(|(name, _)| name == enum_name).unwrap();
+			assert!(
+				source.contains(&format!("\t{} = ", new)),
+				"{}::{} not found",
+				enum_name,
+				new
+			);
+			assert!(
+				source.contains(&mimic(enum_name, old)),
+				"{}::{} isn't deprecated",
+				enum_name,
+				old
+			);
+		}
+	}
+}
@@ -0,0 +1,56 @@
+//! Development tasks for rust-sc2, run with `cargo xtask <task>`.
+//!
+//! Tasks:
+//! - `ids [path to stableid.json]` — regenerates `src/ids` module.
+//!   By default `stableid.json` is taken from `Documents/StarCraft II` in home directory.
+
+mod ids;
+
+use std::{
+	env, fs,
+	path::{Path, PathBuf},
+	process,
+};
+
+const USAGE: &str = "Usage: cargo xtask ids [path to stableid.json]";
+
+fn main() {
+	let args = env::args().skip(1).collect::<Vec<_>>();
+	let result = match args.first().map(String::as_str) {
+		Some("ids") => gen_ids(args.get(1).map(PathBuf::from)),
+		_ => Err(USAGE.to_string()),
+	};
+	if let Err(e) = result {
+		eprintln!("{}", e);
+		process::exit(1);
+	}
+}
+
+fn default_stableid() -> Option<PathBuf> {
+	let home = env::var_os("HOME").or_else(|| env::var_os("USERPROFILE"))?;
+	Some(
+		Path::new(&home)
+			.join("Documents")
+			.join("StarCraft II")
+			.join("stableid.json"),
+	)
+}
+
+fn gen_ids(path: Option<PathBuf>) -> Result<(), String> {
+	let path = path
+		.or_else(default_stableid)
+		.ok_or("Can't find home directory, specify path to stableid.json")?;
+	let source = fs::read_to_string(&path).map_err(|e| format!("Can't read {}: {}", path.display(), e))?;
+	let data = serde_json::from_str(&source).map_err(|e| format!("Can't parse {}: {}", path.display(), e))?;
+
+	let dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+		.join("..")
+		.join("src")
+		.join("ids");
+	for (file, source) in ids::generate(&data)? {
+		let file = dir.join(file);
+		fs::write(&file, source).map_err(|e| format!("Can't write {}: {}", file.display(), e))?;
+	}
+	println!("Generated ids from {}", path.display());
+	Ok(())
+}